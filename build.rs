@@ -0,0 +1,60 @@
+//! Generates the module list of the `solutions` binary from the scaffolded days in `src/bin`.
+//! Solutions are named `NN.rs` for the default year, and `YYYY-NN.rs` for other years.
+use std::{env, fs, path::Path};
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    let mut names: Vec<String> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter_map(|entry| {
+                    let name = entry.file_name().into_string().ok()?;
+                    let name = name.strip_suffix(".rs")?;
+                    is_solution(name).then(|| name.to_string())
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    names.sort_unstable();
+
+    let mut modules = String::new();
+    let mut registrations = String::new();
+
+    for name in names {
+        let path = bin_dir.join(format!("{name}.rs"));
+        let module = format!("day_{}", name.replace('-', "_"));
+        modules.push_str(&format!(
+            "#[path = {:?}]\n#[allow(dead_code)]\nmod {module};\n",
+            path.display().to_string()
+        ));
+        registrations.push_str(&format!("    {module}::register(registry);\n"));
+    }
+
+    let generated = format!(
+        "{modules}\n/// Registers every scaffolded day.\nfn register_all(registry: &mut advent_of_code::template::registry::Registry) {{\n{registrations}}}\n"
+    );
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("solutions.rs"), generated).unwrap();
+}
+
+/// Whether a file name (without extension) is `NN` or `YYYY-NN` with a valid day.
+fn is_solution(name: &str) -> bool {
+    let day = match name.split_once('-') {
+        Some((year, day)) => {
+            if year.len() != 4 || !year.bytes().all(|b| b.is_ascii_digit()) {
+                return false;
+            }
+            day
+        }
+        None => name,
+    };
+
+    day.len() == 2 && day.parse::<u8>().is_ok_and(|day| (1..=25).contains(&day))
+}
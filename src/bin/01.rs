@@ -1,20 +1,18 @@
 advent_of_code::solution!(1);
 
 pub fn part_one(input: &str) -> Option<u32> {
-    Some(
-        input
-            .lines()
-            .map(|line| {
-                let numbers: Vec<u32> = line
-                    .chars()
-                    .filter(|c| c.is_numeric())
-                    .map(|c| c.to_digit(10).unwrap())
-                    .collect();
-
-                numbers[0] * 10 + numbers.last().unwrap()
-            })
-            .sum(),
-    )
+    input
+        .lines()
+        .map(|line| {
+            let numbers: Vec<u32> = line
+                .chars()
+                .filter(|c| c.is_numeric())
+                .map(|c| c.to_digit(10).unwrap())
+                .collect();
+
+            Some(numbers.first()? * 10 + numbers.last()?)
+        })
+        .sum()
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
@@ -52,21 +50,7 @@ pub fn part_two(input: &str) -> Option<u32> {
     )
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(142));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
-        assert_eq!(result, Some(281));
-    }
+advent_of_code::examples! {
+    part_one,
+    part_two: [2],
 }
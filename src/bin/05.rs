@@ -83,7 +83,7 @@ pub fn part_two(input: &str) -> Option<usize> {
     let seeds: Vec<Range<usize>> = seeds
         .into_iter()
         .tuples()
-        .map(|(start, length)| (start..start + length))
+        .map(|(start, length)| start..start + length)
         .collect();
 
     maps.fold(seeds, |ranges, maps| {
@@ -124,19 +124,7 @@ pub fn part_two(input: &str) -> Option<usize> {
     .min()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(35));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(46));
-    }
+advent_of_code::examples! {
+    part_one,
+    part_two,
 }
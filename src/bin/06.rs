@@ -67,19 +67,7 @@ pub fn part_two(input: &str) -> Option<usize> {
     Some(time - (2 * low) + 1)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(288));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(71503));
-    }
+advent_of_code::examples! {
+    part_one,
+    part_two,
 }
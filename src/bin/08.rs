@@ -145,12 +145,11 @@ pub fn part_one(input: &str) -> Option<usize> {
 
     let (sequence, transitions) = parse(input, &mut registry);
 
-    Some(solve(
-        *registry.starts.iter().next().unwrap(),
-        &transitions,
-        &sequence,
-        |node| registry.targets.contains(&node),
-    ))
+    let start = registry.starts.iter().next()?;
+
+    Some(solve(*start, &transitions, &sequence, |node| {
+        registry.targets.contains(&node)
+    }))
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
@@ -174,28 +173,7 @@ pub fn part_two(input: &str) -> Option<usize> {
     )
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
-        ));
-        assert_eq!(result, Some(2));
-
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
-        assert_eq!(result, Some(6));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 3,
-        ));
-        assert_eq!(result, Some(6));
-    }
+advent_of_code::examples! {
+    part_one: [1, 2],
+    part_two: [3],
 }
@@ -1,5 +1,4 @@
 use itertools::Itertools;
-use std::isize;
 advent_of_code::solution!(10);
 
 #[derive(Clone, Copy, Debug)]
@@ -42,7 +41,7 @@ impl Grid {
     }
 }
 
-fn parse_grid(input: &str) -> (Coordinate, Grid) {
+fn parse_grid(input: &str) -> Result<(Coordinate, Grid), String> {
     let mut start = None;
 
     let tiles = input
@@ -52,24 +51,25 @@ fn parse_grid(input: &str) -> (Coordinate, Grid) {
             line.chars()
                 .enumerate()
                 .map(|(x, char)| match char {
-                    '.' => Tile::Ground,
+                    '.' => Ok(Tile::Ground),
                     'S' => {
                         start = Some((x as isize, y as isize));
-                        Tile::Start
+                        Ok(Tile::Start)
                     }
-                    '|' => Tile::Pipe(Pipe::Vertical),
-                    '-' => Tile::Pipe(Pipe::Horizontal),
-                    'L' => Tile::Pipe(Pipe::NorthEast),
-                    'J' => Tile::Pipe(Pipe::NorthWest),
-                    '7' => Tile::Pipe(Pipe::SouthWest),
-                    'F' => Tile::Pipe(Pipe::SouthEast),
-                    _ => panic!(),
+                    '|' => Ok(Tile::Pipe(Pipe::Vertical)),
+                    '-' => Ok(Tile::Pipe(Pipe::Horizontal)),
+                    'L' => Ok(Tile::Pipe(Pipe::NorthEast)),
+                    'J' => Ok(Tile::Pipe(Pipe::NorthWest)),
+                    '7' => Ok(Tile::Pipe(Pipe::SouthWest)),
+                    'F' => Ok(Tile::Pipe(Pipe::SouthEast)),
+                    _ => Err(format!("unexpected tile '{char}' at {x},{y}")),
                 })
                 .collect()
         })
-        .collect();
+        .collect::<Result<_, _>>()?;
 
-    (start.unwrap(), Grid::new(tiles))
+    let start = start.ok_or("no start tile 'S' in the input")?;
+    Ok((start, Grid::new(tiles)))
 }
 
 fn neighbours(coordinate: Coordinate, pipe: Pipe) -> (Coordinate, Coordinate) {
@@ -122,8 +122,8 @@ fn find_cycle_starting_at(
     Some(cycle)
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let (start_coordinate, grid) = parse_grid(input);
+pub fn part_one(input: &str) -> Result<usize, String> {
+    let (start_coordinate, grid) = parse_grid(input)?;
 
     let all_pipes = [
         Pipe::Horizontal,
@@ -139,10 +139,11 @@ pub fn part_one(input: &str) -> Option<usize> {
         .filter_map(|pipe| find_cycle_starting_at(start_coordinate, pipe, &grid))
         .next()
         .map(|cycle| cycle.len() / 2)
+        .ok_or_else(|| "no loop passes through the start tile".to_string())
 }
 
-pub fn part_two(input: &str) -> Option<isize> {
-    let (start_coordinate, grid) = parse_grid(input);
+pub fn part_two(input: &str) -> Result<isize, String> {
+    let (start_coordinate, grid) = parse_grid(input)?;
 
     let all_pipes = [
         Pipe::Horizontal,
@@ -157,7 +158,7 @@ pub fn part_two(input: &str) -> Option<isize> {
         .into_iter()
         .filter_map(|pipe| find_cycle_starting_at(start_coordinate, pipe, &grid))
         .next()
-        .unwrap();
+        .ok_or("no loop passes through the start tile")?;
 
     // Pick's theorem enables us to easily compute the number of points based on the area of the
     //  polygon which is obtained using Shoelace's algorithm.
@@ -168,50 +169,10 @@ pub fn part_two(input: &str) -> Option<isize> {
         .map(|((x1, y1), (x2, y2))| (y1 + y2) * (x1 - x2))
         .sum();
 
-    Some((area.abs() - cycle.len() as isize) / 2 + 1)
+    Ok((area.abs() - cycle.len() as isize) / 2 + 1)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one_1() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
-        ));
-        assert_eq!(result, Some(4));
-    }
-
-    #[test]
-    fn test_part_one_2() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
-        assert_eq!(result, Some(8));
-    }
-
-    #[test]
-    fn test_part_two_1() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 3,
-        ));
-        assert_eq!(result, Some(4));
-    }
-
-    #[test]
-    fn test_part_two_2() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 4,
-        ));
-        assert_eq!(result, Some(8));
-    }
-
-    #[test]
-    fn test_part_two_3() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 5,
-        ));
-        assert_eq!(result, Some(10));
-    }
+advent_of_code::examples! {
+    part_one: [1, 2],
+    part_two: [3, 4, 5],
 }
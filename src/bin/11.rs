@@ -1,6 +1,5 @@
 use itertools::Itertools;
 use rustc_hash::FxHashSet;
-use std::cmp::{max, min};
 advent_of_code::solution!(11);
 
 fn solve(input: &str, expansion: isize) -> isize {
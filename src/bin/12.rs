@@ -167,6 +167,11 @@ pub fn part_two(input: &str) -> Option<usize> {
     )
 }
 
+advent_of_code::examples! {
+    part_one,
+    part_two,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -179,9 +184,6 @@ mod tests {
         assert_eq!(part_one("????.#...#... 4,1,1"), Some(1));
         assert_eq!(part_one("????.######..#####. 1,6,5"), Some(4));
         assert_eq!(part_one("?###???????? 3,2,1"), Some(10));
-
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(21));
     }
 
     #[test]
@@ -192,8 +194,5 @@ mod tests {
         assert_eq!(part_two("????.#...#... 4,1,1"), Some(16));
         assert_eq!(part_two("????.######..#####. 1,6,5"), Some(2500));
         assert_eq!(part_two("?###???????? 3,2,1"), Some(506250));
-
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(525152));
     }
 }
@@ -1,8 +1,9 @@
+use advent_of_code::template::Solution;
 use itertools::Itertools;
 use rustc_hash::FxHashMap;
 use std::cmp::min;
 use std::collections::VecDeque;
-advent_of_code::solution!(14);
+advent_of_code::solution!(14 => Dish);
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum Space {
@@ -88,138 +89,147 @@ fn roll_towards(
     }
 }
 
-pub fn part_one(input: &str) -> Option<isize> {
-    let mut grid: FxHashMap<Coordinate, Space> = input
-        .lines()
-        .enumerate()
-        .flat_map(|(y, line)| {
-            line.chars()
-                .enumerate()
-                .filter_map(move |(x, char)| match char {
-                    '.' => None,
-                    '#' => Some(((x as isize, y as isize), Space::FixedRock)),
-                    'O' => Some(((x as isize, y as isize), Space::RollingRock)),
-                    _ => panic!(),
-                })
-        })
-        .collect();
-
-    let height = grid.keys().map(|(_, y)| *y).max().unwrap();
-    let width = grid.keys().map(|(x, _)| *x).max().unwrap();
-
-    roll_towards(&mut grid, height, width, Direction::North);
-
-    Some(
-        grid.iter()
-            .filter(|(_, rock)| match rock {
-                Space::FixedRock => false,
-                Space::RollingRock => true,
-            })
-            .map(|((_, y), _)| height - *y + 1)
-            .sorted()
-            .sum(),
-    )
+struct Platform {
+    grid: FxHashMap<Coordinate, Space>,
+    height: isize,
+    width: isize,
 }
 
-pub fn part_two(input: &str) -> Option<isize> {
-    let mut grid: FxHashMap<Coordinate, Space> = input
-        .lines()
-        .enumerate()
-        .flat_map(|(y, line)| {
-            line.chars()
-                .enumerate()
-                .filter_map(move |(x, char)| match char {
-                    '.' => None,
-                    '#' => Some(((x as isize, y as isize), Space::FixedRock)),
-                    'O' => Some(((x as isize, y as isize), Space::RollingRock)),
-                    _ => panic!(),
+struct Dish;
+
+impl Solution for Dish {
+    type Parsed = Platform;
+    type Error = String;
+    type OutputOne = Option<isize>;
+    type OutputTwo = Option<isize>;
+
+    fn parse(input: &str) -> Result<Platform, String> {
+        let grid: FxHashMap<Coordinate, Space> = input
+            .lines()
+            .enumerate()
+            .flat_map(|(y, line)| {
+                line.chars().enumerate().filter_map(move |(x, char)| {
+                    let coordinate = (x as isize, y as isize);
+
+                    match char {
+                        '.' => None,
+                        '#' => Some(Ok((coordinate, Space::FixedRock))),
+                        'O' => Some(Ok((coordinate, Space::RollingRock))),
+                        _ => Some(Err(format!("unexpected space '{char}' at {x},{y}"))),
+                    }
                 })
+            })
+            .collect::<Result<_, _>>()?;
+
+        let height = grid
+            .keys()
+            .map(|(_, y)| *y)
+            .max()
+            .ok_or("the platform has no rocks")?;
+        let width = grid
+            .keys()
+            .map(|(x, _)| *x)
+            .max()
+            .ok_or("the platform has no rocks")?;
+
+        Ok(Platform {
+            grid,
+            height,
+            width,
         })
-        .collect();
+    }
 
-    let height = grid.keys().map(|(_, y)| *y).max().unwrap();
-    let width = grid.keys().map(|(_, y)| *y).max().unwrap();
+    fn part_one(platform: &Platform) -> Option<isize> {
+        let mut grid = platform.grid.clone();
+        let (height, width) = (platform.height, platform.width);
 
-    let mut history_of_load: Vec<isize> = vec![];
+        roll_towards(&mut grid, height, width, Direction::North);
 
-    let cycles_required = 1000000000;
-    let mut cycles_remaining = 1000000000;
-    let mut cycle_found = false;
+        Some(
+            grid.iter()
+                .filter(|(_, rock)| match rock {
+                    Space::FixedRock => false,
+                    Space::RollingRock => true,
+                })
+                .map(|((_, y), _)| height - *y + 1)
+                .sorted()
+                .sum(),
+        )
+    }
 
-    while cycles_remaining > 0 {
-        cycles_remaining -= 1;
+    fn part_two(platform: &Platform) -> Option<isize> {
+        let mut grid = platform.grid.clone();
+        let (height, width) = (platform.height, platform.width);
 
-        for direction in [
-            Direction::North,
-            Direction::West,
-            Direction::South,
-            Direction::East,
-        ] {
-            roll_towards(&mut grid, height, width, direction);
-        }
+        let mut history_of_load: Vec<isize> = vec![];
 
-        if cycle_found {
-            continue;
-        }
+        let cycles_required = 1000000000;
+        let mut cycles_remaining = 1000000000;
+        let mut cycle_found = false;
 
-        let load = grid
-            .iter()
-            .filter(|(_, rock)| match rock {
-                Space::FixedRock => false,
-                Space::RollingRock => true,
-            })
-            .map(|((_, y), _)| height - *y + 1)
-            .sum::<isize>();
+        while cycles_remaining > 0 {
+            cycles_remaining -= 1;
 
-        history_of_load.push(load);
+            for direction in [
+                Direction::North,
+                Direction::West,
+                Direction::South,
+                Direction::East,
+            ] {
+                roll_towards(&mut grid, height, width, direction);
+            }
 
-        let cycle_length = (2..min(20, history_of_load.len() / 2)).find(|cycle_length| {
-            if history_of_load.len() <= cycle_length * 2 {
-                return false;
+            if cycle_found {
+                continue;
             }
 
-            (0..*cycle_length).all(|offset| {
-                let current = history_of_load[history_of_load.len() - offset - 1];
-                let predecessor =
-                    history_of_load[history_of_load.len() - cycle_length - offset - 1];
+            let load = grid
+                .iter()
+                .filter(|(_, rock)| match rock {
+                    Space::FixedRock => false,
+                    Space::RollingRock => true,
+                })
+                .map(|((_, y), _)| height - *y + 1)
+                .sum::<isize>();
 
-                current == predecessor
-            })
-        });
+            history_of_load.push(load);
 
-        if let Some(cycle_length) = cycle_length {
-            let iteration = cycles_required - cycles_remaining;
-            let number_of_cycles_remaining = (cycles_required - iteration) % cycle_length;
+            let cycle_length = (2..min(20, history_of_load.len() / 2)).find(|cycle_length| {
+                if history_of_load.len() <= cycle_length * 2 {
+                    return false;
+                }
 
-            cycles_remaining = number_of_cycles_remaining;
-            cycle_found = true;
-        }
-    }
+                (0..*cycle_length).all(|offset| {
+                    let current = history_of_load[history_of_load.len() - offset - 1];
+                    let predecessor =
+                        history_of_load[history_of_load.len() - cycle_length - offset - 1];
 
-    Some(
-        grid.iter()
-            .filter(|(_, rock)| match rock {
-                Space::FixedRock => false,
-                Space::RollingRock => true,
-            })
-            .map(|((_, y), _)| height - *y + 1)
-            .sum::<isize>(),
-    )
-}
+                    current == predecessor
+                })
+            });
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+            if let Some(cycle_length) = cycle_length {
+                let iteration = cycles_required - cycles_remaining;
+                let number_of_cycles_remaining = (cycles_required - iteration) % cycle_length;
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(136));
-    }
+                cycles_remaining = number_of_cycles_remaining;
+                cycle_found = true;
+            }
+        }
 
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(64));
+        Some(
+            grid.iter()
+                .filter(|(_, rock)| match rock {
+                    Space::FixedRock => false,
+                    Space::RollingRock => true,
+                })
+                .map(|((_, y), _)| height - *y + 1)
+                .sum::<isize>(),
+        )
     }
 }
+
+advent_of_code::examples! {
+    part_one,
+    part_two,
+}
@@ -1,5 +1,5 @@
 use rustc_hash::FxHashMap;
-advent_of_code::solution!(15);
+advent_of_code::solution!(15, trim_newline);
 
 fn hash(input: &str) -> u32 {
     input
@@ -96,6 +96,11 @@ pub fn part_two(input: &str) -> Option<usize> {
     )
 }
 
+advent_of_code::examples! {
+    part_one,
+    part_two,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -104,14 +109,5 @@ mod tests {
     fn test_part_one() {
         let result = part_one("HASH");
         assert_eq!(result, Some(52));
-
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(1320));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(145));
     }
 }
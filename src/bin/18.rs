@@ -85,19 +85,7 @@ pub fn part_two(input: &str) -> Option<isize> {
     Some(content_of_lagoon(&data))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(62));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(952408144115));
-    }
+advent_of_code::examples! {
+    part_one,
+    part_two,
 }
@@ -1,7 +1,8 @@
+use advent_of_code::template::Solution;
 use rustc_hash::FxHashMap;
 use std::cmp::{max, min};
 use std::collections::VecDeque;
-advent_of_code::solution!(19);
+advent_of_code::solution!(19 => Sorting);
 
 #[derive(Debug)]
 enum Value {
@@ -27,82 +28,109 @@ enum Reference {
 
 type Rating = [usize; 4];
 
-fn parse_input(input: &str) -> (FxHashMap<String, Vec<Reference>>, Vec<Rating>) {
-    let (rules, ratings) = input.split_once("\n\n").unwrap();
+type Rules = FxHashMap<String, Vec<Reference>>;
 
-    let rules: FxHashMap<String, Vec<Reference>> = rules
-        .lines()
-        .map(|line| {
-            let (name, data) = line.split_once('{').unwrap();
-            let references = data
-                .split(',')
-                .map(|reference| {
-                    let reference = {
-                        if reference.ends_with('}') {
-                            reference[0..reference.len() - 1].to_string()
-                        } else {
-                            reference.to_string()
-                        }
-                    };
+fn parse_reference(reference: &str) -> Result<Reference, String> {
+    let reference = reference.strip_suffix('}').unwrap_or(reference);
 
-                    if reference == "A" {
-                        return Reference::Accept;
-                    }
-
-                    if reference == "R" {
-                        return Reference::Reject;
-                    }
+    if reference == "A" {
+        return Ok(Reference::Accept);
+    }
 
-                    if !reference.contains(':') {
-                        return Reference::Other(reference);
-                    }
+    if reference == "R" {
+        return Ok(Reference::Reject);
+    }
 
-                    let (value_and_condition, name) = reference.split_once(':').unwrap();
-                    let value = match value_and_condition.chars().next().unwrap() {
-                        'x' => Value::X,
-                        'm' => Value::M,
-                        'a' => Value::A,
-                        's' => Value::S,
-                        _ => panic!("{value_and_condition}"),
-                    };
-
-                    let condition = match value_and_condition.chars().nth(1).unwrap() {
-                        '<' => Condition::Lower,
-                        '>' => Condition::Higher,
-                        _ => panic!(),
-                    };
-
-                    Reference::Conditional(
-                        value,
-                        condition,
-                        value_and_condition[2..value_and_condition.len()]
-                            .parse()
-                            .unwrap(),
-                        name.to_string(),
-                    )
-                })
-                .collect();
+    let Some((value_and_condition, name)) = reference.split_once(':') else {
+        return Ok(Reference::Other(reference.to_string()));
+    };
+
+    let mut chars = value_and_condition.chars();
+
+    let value = match chars.next() {
+        Some('x') => Value::X,
+        Some('m') => Value::M,
+        Some('a') => Value::A,
+        Some('s') => Value::S,
+        _ => return Err(format!("unexpected rating in rule \"{reference}\"")),
+    };
+
+    let condition = match chars.next() {
+        Some('<') => Condition::Lower,
+        Some('>') => Condition::Higher,
+        _ => return Err(format!("unexpected condition in rule \"{reference}\"")),
+    };
+
+    let threshold = chars
+        .as_str()
+        .parse()
+        .map_err(|e| format!("invalid threshold in rule \"{reference}\": {e}"))?;
+
+    Ok(Reference::Conditional(
+        value,
+        condition,
+        threshold,
+        name.to_string(),
+    ))
+}
 
-            (name.to_string(), references)
+fn parse_rating(line: &str) -> Result<Rating, String> {
+    let values = line
+        .strip_prefix('{')
+        .and_then(|line| line.strip_suffix('}'))
+        .ok_or_else(|| format!("rating \"{line}\" is not enclosed in braces"))?;
+
+    let values: Vec<usize> = values
+        .split(',')
+        .map(|value| {
+            let (_, value) = value
+                .split_once('=')
+                .ok_or_else(|| format!("expected a category and a value in \"{value}\""))?;
+            value
+                .parse()
+                .map_err(|e| format!("invalid value \"{value}\" in rating \"{line}\": {e}"))
         })
-        .chain(std::iter::once(("A".to_string(), vec![Reference::Accept])))
-        .chain(std::iter::once(("R".to_string(), vec![Reference::Reject])))
-        .collect();
+        .collect::<Result<_, String>>()?;
 
-    let ratings: Vec<Rating> = ratings
+    values
+        .try_into()
+        .map_err(|_| format!("rating \"{line}\" does not have four categories"))
+}
+
+fn parse_input(input: &str) -> Result<(Rules, Vec<Rating>), String> {
+    let (rules, ratings) = input
+        .split_once("\n\n")
+        .ok_or("expected the rules and the ratings separated by an empty line")?;
+
+    let rules: FxHashMap<String, Vec<Reference>> = rules
         .lines()
         .map(|line| {
-            let values: Vec<usize> = line[1..line.len() - 1]
+            let (name, data) = line
+                .split_once('{')
+                .ok_or_else(|| format!("expected a name before the rules of \"{line}\""))?;
+            let references = data
                 .split(',')
-                .map(|value| value.split_once('=').unwrap().1)
-                .map(|value| value.parse().unwrap())
-                .collect();
+                .map(parse_reference)
+                .collect::<Result<_, _>>()?;
 
-            [values[0], values[1], values[2], values[3]]
+            Ok((name.to_string(), references))
         })
-        .collect();
+        .chain(std::iter::once(Ok((
+            "A".to_string(),
+            vec![Reference::Accept],
+        ))))
+        .chain(std::iter::once(Ok((
+            "R".to_string(),
+            vec![Reference::Reject],
+        ))))
+        .collect::<Result<_, String>>()?;
+
+    let ratings: Vec<Rating> = ratings
+        .lines()
+        .map(parse_rating)
+        .collect::<Result<_, _>>()?;
 
-    (rules, ratings)
+    Ok((rules, ratings))
 }
 
 fn solve_for_rule(rating: Rating, rules: &FxHashMap<String, Vec<Reference>>, name: &str) -> bool {
@@ -136,119 +164,119 @@ fn solve_for_rule(rating: Rating, rules: &FxHashMap<String, Vec<Reference>>, nam
     panic!();
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let (rules, ratings) = parse_input(input);
+struct Sorting;
 
-    Some(
-        ratings
-            .into_iter()
-            .filter(|rating| solve_for_rule(*rating, &rules, "in"))
-            .map(|rating| rating.into_iter().sum::<usize>())
-            .sum(),
-    )
-}
+impl Solution for Sorting {
+    type Parsed = (Rules, Vec<Rating>);
+    type Error = String;
+    type OutputOne = Option<usize>;
+    type OutputTwo = Option<usize>;
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let (rules, _) = parse_input(input);
-
-    let mut queue: VecDeque<([(usize, usize); 4], String)> = VecDeque::new();
-    let mut feasible_ranges: Vec<[(usize, usize); 4]> = Vec::new();
-    queue.push_front(([(1, 4000); 4], "in".to_string()));
-
-    while let Some((values, name)) = queue.pop_front() {
-        let mut remaining_values = Some(values);
-        let rules = rules.get(&name).unwrap();
-
-        for reference in rules {
-            let Some(values) = remaining_values else {
-                break;
-            };
-
-            match reference {
-                Reference::Other(name) => queue.push_front((values, name.to_string())),
-                Reference::Accept => feasible_ranges.push(values),
-                Reference::Reject => continue,
-                Reference::Conditional(value, condition, threshold, target) => {
-                    let index = match value {
-                        Value::X => 0,
-                        Value::M => 1,
-                        Value::A => 2,
-                        Value::S => 3,
-                    };
-
-                    let (current_min, current_max) = values[index];
-
-                    let mut accepted_range = None;
-                    let mut remaining_range = None;
-
-                    match condition {
-                        Condition::Higher => {
-                            if current_max > *threshold {
-                                accepted_range =
-                                    Some((max(*threshold + 1, current_min), current_max));
-                            }
+    fn parse(input: &str) -> Result<Self::Parsed, String> {
+        parse_input(input)
+    }
+
+    fn part_one((rules, ratings): &Self::Parsed) -> Option<usize> {
+        Some(
+            ratings
+                .iter()
+                .copied()
+                .filter(|rating| solve_for_rule(*rating, rules, "in"))
+                .map(|rating| rating.into_iter().sum::<usize>())
+                .sum(),
+        )
+    }
+
+    fn part_two((rules, _): &Self::Parsed) -> Option<usize> {
+        let mut queue: VecDeque<([(usize, usize); 4], String)> = VecDeque::new();
+        let mut feasible_ranges: Vec<[(usize, usize); 4]> = Vec::new();
+        queue.push_front(([(1, 4000); 4], "in".to_string()));
+
+        while let Some((values, name)) = queue.pop_front() {
+            let mut remaining_values = Some(values);
+            let rules = rules.get(&name).unwrap();
 
-                            if current_min <= *threshold {
-                                remaining_range = Some((current_min, min(*threshold, current_max)));
+            for reference in rules {
+                let Some(values) = remaining_values else {
+                    break;
+                };
+
+                match reference {
+                    Reference::Other(name) => queue.push_front((values, name.to_string())),
+                    Reference::Accept => feasible_ranges.push(values),
+                    Reference::Reject => continue,
+                    Reference::Conditional(value, condition, threshold, target) => {
+                        let index = match value {
+                            Value::X => 0,
+                            Value::M => 1,
+                            Value::A => 2,
+                            Value::S => 3,
+                        };
+
+                        let (current_min, current_max) = values[index];
+
+                        let mut accepted_range = None;
+                        let mut remaining_range = None;
+
+                        match condition {
+                            Condition::Higher => {
+                                if current_max > *threshold {
+                                    accepted_range =
+                                        Some((max(*threshold + 1, current_min), current_max));
+                                }
+
+                                if current_min <= *threshold {
+                                    remaining_range =
+                                        Some((current_min, min(*threshold, current_max)));
+                                }
                             }
-                        }
-                        Condition::Lower => {
-                            if current_min < *threshold {
-                                accepted_range =
-                                    Some((current_min, min(*threshold - 1, current_max)));
+                            Condition::Lower => {
+                                if current_min < *threshold {
+                                    accepted_range =
+                                        Some((current_min, min(*threshold - 1, current_max)));
+                                }
+
+                                if current_max >= *threshold {
+                                    remaining_range =
+                                        Some((max(*threshold, current_min), current_max));
+                                }
                             }
+                        };
 
-                            if current_max >= *threshold {
-                                remaining_range = Some((max(*threshold, current_min), current_max));
-                            }
+                        if let Some(accepted_range) = accepted_range {
+                            let mut temp = values;
+                            temp[index] = accepted_range;
+                            queue.push_front((temp, target.clone()));
                         }
-                    };
-
-                    if let Some(accepted_range) = accepted_range {
-                        let mut temp = values;
-                        temp[index] = accepted_range;
-                        queue.push_front((temp, target.clone()));
-                    }
 
-                    if let Some(remaining_range) = remaining_range {
-                        let mut temp = values;
-                        temp[index] = remaining_range;
+                        if let Some(remaining_range) = remaining_range {
+                            let mut temp = values;
+                            temp[index] = remaining_range;
 
-                        remaining_values = Some(temp);
-                    } else {
-                        remaining_values = None;
+                            remaining_values = Some(temp);
+                        } else {
+                            remaining_values = None;
+                        }
                     }
-                }
-            };
+                };
+            }
         }
-    }
 
-    Some(
-        feasible_ranges
-            .iter()
-            .map(|range| {
-                range
-                    .iter()
-                    .map(|(min, max)| max - min + 1)
-                    .product::<usize>()
-            })
-            .sum(),
-    )
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(19114));
+        Some(
+            feasible_ranges
+                .iter()
+                .map(|range| {
+                    range
+                        .iter()
+                        .map(|(min, max)| max - min + 1)
+                        .product::<usize>()
+                })
+                .sum(),
+        )
     }
+}
 
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(167409079868000));
-    }
+advent_of_code::examples! {
+    part_one,
+    part_two,
 }
@@ -254,19 +254,7 @@ pub fn part_two(input: &str) -> Option<usize> {
     Some(maximum_length)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(94));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(154));
-    }
+advent_of_code::examples! {
+    part_one,
+    part_two,
 }
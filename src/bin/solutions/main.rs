@@ -0,0 +1,12 @@
+//! Runs any subset of the scaffolded solutions within a single process.
+//! The list of days is generated by `build.rs` from the files in `src/bin`.
+use advent_of_code::template::default_year;
+use advent_of_code::template::registry::Registry;
+
+include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+
+fn main() {
+    let mut registry = Registry::new(default_year());
+    register_all(&mut registry);
+    registry.main();
+}
@@ -1,6 +1,14 @@
 use std::error::Error;
 use std::fmt::Display;
 use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::{PuzzleId, Year};
+
+/// Puzzles unlock at midnight in UTC-5 (US Eastern Standard Time), daylight saving time does not apply in December.
+const UNLOCK_OFFSET: Duration = Duration::from_secs(5 * 60 * 60);
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
 
 /// A valid day number of advent (i.e. an integer in range 1 to 25).
 ///
@@ -35,6 +43,74 @@ impl Day {
     pub fn into_inner(self) -> u8 {
         self.0
     }
+
+    /// Returns the instant the puzzle of this day unlocks in the given year.
+    ///
+    /// ```
+    /// # use advent_of_code::{day, year};
+    /// # use std::time::{Duration, UNIX_EPOCH};
+    /// // 2023-12-01T05:00:00Z
+    /// assert_eq!(day!(1).unlock_time(year!(2023)), UNIX_EPOCH + Duration::from_secs(1_701_406_800));
+    /// ```
+    pub fn unlock_time(self, year: Year) -> SystemTime {
+        let days = days_since_epoch(year.into_inner(), 12, self.0);
+        UNIX_EPOCH + Duration::from_secs(days * SECONDS_PER_DAY) + UNLOCK_OFFSET
+    }
+
+    /// Whether the puzzle of this day in the given year has unlocked at `now`.
+    pub fn is_unlocked(self, year: Year, now: SystemTime) -> bool {
+        now >= self.unlock_time(year)
+    }
+
+    /// Returns the puzzle that unlocked on the date of `now` in UTC-5, or [`None`] outside of advent.
+    pub fn today(now: SystemTime) -> Option<PuzzleId> {
+        let secs = now
+            .duration_since(UNIX_EPOCH)
+            .ok()?
+            .checked_sub(UNLOCK_OFFSET)?;
+        let (year, month, day) = civil_from_days(secs.as_secs() / SECONDS_PER_DAY);
+
+        if month != 12 {
+            return None;
+        }
+
+        Some(PuzzleId::new(Year::new(year)?, Day::new(day)?))
+    }
+}
+
+/// The number of days between the unix epoch and a date in the gregorian calendar on or after it.
+fn days_since_epoch(year: u16, month: u8, day: u8) -> u64 {
+    // see http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+    let year = u64::from(year) - u64::from(month <= 2);
+    let month = u64::from(month);
+    let era = year / 400;
+    let year_of_era = year - era * 400;
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + u64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// The date in the gregorian calendar a number of days after the unix epoch, the inverse of [`days_since_epoch`].
+fn civil_from_days(days: u64) -> (u16, u8, u8) {
+    // see http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    // the values are bounded by the calendar, only the year can overflow for instants far in the future.
+    (
+        u16::try_from(year).unwrap_or(u16::MAX),
+        u8::try_from(month).unwrap(),
+        u8::try_from(day).unwrap(),
+    )
 }
 
 impl Display for Day {
@@ -114,6 +190,69 @@ impl Iterator for AllDays {
 
 /* -------------------------------------------------------------------------- */
 
+/// A part of a day's puzzle. Every day has two parts, except for the 25th.
+///
+/// # Display
+/// This value displays as its number.
+///
+/// ```
+/// # use advent_of_code::Part;
+/// assert_eq!(Part::Two.to_string(), "2")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    /// Creates a [`Part`] from its number, returns [`None`] for anything but 1 and 2.
+    pub fn new(part: u8) -> Option<Self> {
+        match part {
+            1 => Some(Part::One),
+            2 => Some(Part::Two),
+            _ => None,
+        }
+    }
+
+    /// Converts the [`Part`] into its number.
+    pub fn into_inner(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.into_inner())
+    }
+}
+
+impl FromStr for Part {
+    type Err = PartFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let part = s.parse().map_err(|_| PartFromStrError)?;
+        Self::new(part).ok_or(PartFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Part`].
+#[derive(Debug)]
+pub struct PartFromStrError;
+
+impl Error for PartFromStrError {}
+
+impl Display for PartFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting part 1 or 2")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 /// Creates a [`Day`] value in a const context.
 #[macro_export]
 macro_rules! day {
@@ -134,7 +273,68 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use super::{all_days, civil_from_days, days_since_epoch, Day, Part};
+    use crate::{day, year, PuzzleId};
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn computes_unlock_time() {
+        // 2023-12-01T05:00:00Z
+        assert_eq!(
+            day!(1).unlock_time(year!(2023)),
+            UNIX_EPOCH + Duration::from_secs(1_701_406_800)
+        );
+        // 2024-12-25T05:00:00Z
+        assert_eq!(
+            day!(25).unlock_time(year!(2024)),
+            UNIX_EPOCH + Duration::from_secs(1_735_102_800)
+        );
+    }
+
+    #[test]
+    fn converts_dates_both_ways() {
+        for date in [(1970, 1, 1), (2000, 2, 29), (2023, 12, 1), (2024, 12, 31)] {
+            assert_eq!(
+                civil_from_days(days_since_epoch(date.0, date.1, date.2)),
+                date
+            );
+        }
+    }
+
+    #[test]
+    fn unlocks_at_midnight_utc_minus_five() {
+        let unlock = day!(8).unlock_time(year!(2023));
+        assert!(!day!(8).is_unlocked(year!(2023), unlock - Duration::from_secs(1)));
+        assert!(day!(8).is_unlocked(year!(2023), unlock));
+    }
+
+    #[test]
+    fn finds_todays_puzzle() {
+        let first = day!(1).unlock_time(year!(2023));
+        let last = day!(25).unlock_time(year!(2023));
+        let one_day = Duration::from_secs(24 * 60 * 60);
+
+        assert_eq!(Day::today(first - Duration::from_secs(1)), None);
+        assert_eq!(Day::today(first), Some(PuzzleId::new(year!(2023), day!(1))));
+        assert_eq!(
+            Day::today(first + one_day - Duration::from_secs(1)),
+            Some(PuzzleId::new(year!(2023), day!(1)))
+        );
+        assert_eq!(
+            Day::today(last + Duration::from_secs(60)),
+            Some(PuzzleId::new(year!(2023), day!(25)))
+        );
+        assert_eq!(Day::today(last + one_day), None);
+    }
+
+    #[test]
+    fn parses_parts() {
+        assert_eq!("1".parse::<Part>().ok(), Some(Part::One));
+        assert_eq!("2".parse::<Part>().ok(), Some(Part::Two));
+        assert!("0".parse::<Part>().is_err());
+        assert!("3".parse::<Part>().is_err());
+        assert!("two".parse::<Part>().is_err());
+    }
 
     #[test]
     fn all_days_iterator() {
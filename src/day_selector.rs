@@ -0,0 +1,226 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::SystemTime;
+
+use crate::{all_days, Day, PuzzleId, Year};
+
+/// Selects the days a command works on.
+///
+/// Selectors are written as a single day (`5`), a range (`3-9`), a list of days and ranges (`1,4,7` or `1-3,8`),
+/// `all`, `today` (the day whose puzzle unlocked last, during advent) or `latest-scaffolded`.
+///
+/// ```
+/// # use advent_of_code::{day, DaySelector};
+/// let selector: DaySelector = "1,3-4".parse().unwrap();
+/// assert_eq!(selector, DaySelector::Days(vec![day!(1), day!(3), day!(4)]));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DaySelector {
+    /// The days in ascending order without duplicates.
+    Days(Vec<Day>),
+    All,
+    Today,
+    LatestScaffolded,
+}
+
+impl DaySelector {
+    /// Resolves the selector for a year at the current time.
+    pub fn days(&self, year: Year) -> Result<SelectedDays, DaySelectorError> {
+        self.resolve(year, SystemTime::now(), |puzzle| {
+            Path::new(&puzzle.bin_path()).exists()
+        })
+    }
+
+    fn resolve(
+        &self,
+        year: Year,
+        now: SystemTime,
+        is_scaffolded: impl Fn(PuzzleId) -> bool,
+    ) -> Result<SelectedDays, DaySelectorError> {
+        let days = match self {
+            DaySelector::Days(days) => days.clone(),
+            DaySelector::All => all_days().collect(),
+            DaySelector::Today => {
+                let today = Day::today(now)
+                    .filter(|puzzle| puzzle.year == year)
+                    .ok_or(DaySelectorError::NotInAdvent(year))?;
+                vec![today.day]
+            }
+            DaySelector::LatestScaffolded => {
+                let day = all_days()
+                    .filter(|day| is_scaffolded(PuzzleId::new(year, *day)))
+                    .last()
+                    .ok_or(DaySelectorError::NothingScaffolded(year))?;
+                vec![day]
+            }
+        };
+
+        Ok(SelectedDays {
+            days: days.into_iter(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for DaySelector {
+    type Err = DaySelectorFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "all" => return Ok(DaySelector::All),
+            "today" => return Ok(DaySelector::Today),
+            "latest-scaffolded" => return Ok(DaySelector::LatestScaffolded),
+            _ => {}
+        }
+
+        let mut days = vec![];
+
+        for item in s.split(',') {
+            let parse = |day: &str| {
+                day.trim()
+                    .parse::<Day>()
+                    .map_err(|_| DaySelectorFromStrError)
+            };
+
+            match item.split_once('-') {
+                Some((first, last)) => {
+                    let (first, last) = (parse(first)?, parse(last)?);
+                    if first > last {
+                        return Err(DaySelectorFromStrError);
+                    }
+                    days.extend(all_days().filter(|day| *day >= first && *day <= last));
+                }
+                None => days.push(parse(item)?),
+            }
+        }
+
+        days.sort_unstable();
+        days.dedup();
+        Ok(DaySelector::Days(days))
+    }
+}
+
+/// An error which can be returned when parsing a [`DaySelector`].
+#[derive(Debug)]
+pub struct DaySelectorFromStrError;
+
+impl Error for DaySelectorFromStrError {}
+
+impl Display for DaySelectorFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            "expecting days between 1 and 25 like `5`, `3-9` or `1,4,7`, `all`, `today` or `latest-scaffolded`",
+        )
+    }
+}
+
+/// An error which can be returned when resolving a [`DaySelector`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum DaySelectorError {
+    NotInAdvent(Year),
+    NothingScaffolded(Year),
+}
+
+impl Error for DaySelectorError {}
+
+impl Display for DaySelectorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DaySelectorError::NotInAdvent(year) => {
+                write!(f, "there is no puzzle of {year} today")
+            }
+            DaySelectorError::NothingScaffolded(year) => {
+                write!(f, "no day of {year} has been scaffolded yet")
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An iterator that yields the days chosen by a [`DaySelector`] in ascending order.
+pub struct SelectedDays {
+    days: std::vec::IntoIter<Day>,
+}
+
+impl Iterator for SelectedDays {
+    type Item = Day;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.days.next()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{DaySelector, DaySelectorError};
+    use crate::{all_days, day, year, Day};
+    use std::time::Duration;
+
+    fn resolve(
+        selector: &str,
+        now_day: Day,
+        scaffolded: &[Day],
+    ) -> Result<Vec<Day>, DaySelectorError> {
+        let now = now_day.unlock_time(year!(2023)) + Duration::from_secs(60);
+        selector
+            .parse::<DaySelector>()
+            .unwrap()
+            .resolve(year!(2023), now, |puzzle| scaffolded.contains(&puzzle.day))
+            .map(Iterator::collect)
+    }
+
+    #[test]
+    fn parses_selectors() {
+        let days =
+            |days: &[u8]| DaySelector::Days(days.iter().map(|d| Day::new(*d).unwrap()).collect());
+
+        assert_eq!("5".parse::<DaySelector>().unwrap(), days(&[5]));
+        assert_eq!("3-6".parse::<DaySelector>().unwrap(), days(&[3, 4, 5, 6]));
+        assert_eq!("7,1,4".parse::<DaySelector>().unwrap(), days(&[1, 4, 7]));
+        assert_eq!("1-2,2,9".parse::<DaySelector>().unwrap(), days(&[1, 2, 9]));
+        assert_eq!("all".parse::<DaySelector>().unwrap(), DaySelector::All);
+        assert_eq!("today".parse::<DaySelector>().unwrap(), DaySelector::Today);
+        assert_eq!(
+            "latest-scaffolded".parse::<DaySelector>().unwrap(),
+            DaySelector::LatestScaffolded
+        );
+
+        for invalid in ["", "0", "26", "9-3", "1,,2", "1-", "yesterday"] {
+            assert!(invalid.parse::<DaySelector>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn resolves_selectors() {
+        assert_eq!(resolve("all", day!(1), &[]), Ok(all_days().collect()));
+        assert_eq!(resolve("today", day!(8), &[]), Ok(vec![day!(8)]));
+        assert_eq!(
+            resolve("latest-scaffolded", day!(1), &[day!(3), day!(12)]),
+            Ok(vec![day!(12)])
+        );
+        assert_eq!(
+            resolve("latest-scaffolded", day!(1), &[]),
+            Err(DaySelectorError::NothingScaffolded(year!(2023)))
+        );
+    }
+
+    #[test]
+    fn finds_no_puzzle_outside_of_advent() {
+        let selector = DaySelector::Today;
+        let after = day!(25).unlock_time(year!(2023)) + Duration::from_secs(24 * 60 * 60);
+        let before = day!(1).unlock_time(year!(2023)) - Duration::from_secs(1);
+
+        for now in [before, after] {
+            assert_eq!(
+                selector.resolve(year!(2023), now, |_| false).err(),
+                Some(DaySelectorError::NotInAdvent(year!(2023)))
+            );
+        }
+    }
+}
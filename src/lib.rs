@@ -1,4 +1,10 @@
 mod day;
+mod day_selector;
+mod puzzle;
 pub mod template;
+mod year;
 
 pub use day::*;
+pub use day_selector::*;
+pub use puzzle::*;
+pub use year::*;
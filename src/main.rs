@@ -1,55 +1,181 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
+use advent_of_code::template::commands::{
+    all, download, read, rollover, scaffold, solve, today, verify,
+};
 use args::{parse, AppArguments};
 
 mod args {
     use std::process;
 
-    use advent_of_code::Day;
+    use advent_of_code::template::bench_history::HistoryOptions;
+    use advent_of_code::template::default_year;
+    use advent_of_code::template::record::OutputFormat;
+    use advent_of_code::template::runner::{BenchConfig, Statistic};
+    use advent_of_code::{Day, DaySelector, Part, PuzzleId, Year};
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            puzzles: Vec<PuzzleId>,
+            refresh: bool,
+            wait: bool,
         },
         Read {
-            day: Day,
+            puzzles: Vec<PuzzleId>,
+            refresh: bool,
         },
         Scaffold {
-            day: Day,
+            puzzles: Vec<PuzzleId>,
+        },
+        Rollover {
+            year: Year,
         },
         Solve {
-            day: Day,
+            puzzles: Vec<PuzzleId>,
             release: bool,
             time: bool,
-            submit: Option<u8>,
+            bench: BenchConfig,
+            format: OutputFormat,
+            submit: Option<Part>,
+            input: Option<String>,
+            examples: bool,
         },
         All {
+            year: Year,
+            days: Vec<Day>,
             release: bool,
             time: bool,
+            bench: BenchConfig,
+            stat: Statistic,
+            jobs: Option<usize>,
+            history: HistoryOptions,
+        },
+        Today,
+        Verify {
+            year: Year,
+            release: bool,
+            record: bool,
         },
     }
 
+    /// Parses `--year <year>`, which defaults to `AOC_YEAR`.
+    fn parse_year(args: &mut pico_args::Arguments) -> Result<Year, pico_args::Error> {
+        Ok(args
+            .opt_value_from_str("--year")?
+            .unwrap_or_else(default_year))
+    }
+
+    /// Parses the days passed as free argument, see [`DaySelector`], together with `--year <year>`.
+    fn parse_puzzles(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Vec<PuzzleId>, Box<dyn std::error::Error>> {
+        // the year is parsed first, so it can be passed before the days.
+        let year = parse_year(args)?;
+        let selector: DaySelector = args.free_from_str()?;
+        Ok(selector
+            .days(year)?
+            .map(|day| PuzzleId::new(year, day))
+            .collect())
+    }
+
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
         let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-                time: args.contains("--time"),
-            },
+            Some("all") => {
+                let year = parse_year(&mut args)?;
+                let release = args.contains("--release");
+                let time = args.contains("--time");
+                let bench = BenchConfig::parse(&mut args)?;
+                let stat = args.opt_value_from_str("--stat")?.unwrap_or_default();
+                let jobs = args.opt_value_from_str("--jobs")?;
+                let history = HistoryOptions::parse(&mut args)?;
+                // the days are optional, so they can only be told apart from options once those are parsed.
+                let selector = args.opt_free_from_str()?.unwrap_or(DaySelector::All);
+
+                AppArguments::All {
+                    year,
+                    days: selector.days(year)?.collect(),
+                    release,
+                    time,
+                    bench,
+                    stat,
+                    jobs,
+                    history,
+                }
+            }
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                puzzles: parse_puzzles(&mut args)?,
+                refresh: args.contains("--refresh"),
+                wait: args.contains("--wait"),
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                puzzles: parse_puzzles(&mut args)?,
+                refresh: args.contains("--refresh"),
+            },
+            Some("rollover") => AppArguments::Rollover {
+                year: args.free_from_str()?,
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                puzzles: parse_puzzles(&mut args)?,
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
+            Some("solve") => {
+                let puzzles = parse_puzzles(&mut args)?;
+                let submit = args.opt_value_from_str("--submit")?;
+
+                if submit.is_some() && puzzles.len() > 1 {
+                    return Err("--submit expects a single day.".into());
+                }
+
+                let input: Option<String> = args.opt_value_from_str("--input")?;
+
+                if input.is_some() && puzzles.len() > 1 {
+                    return Err("--input expects a single day.".into());
+                }
+
+                if input.is_some() && submit.is_some() {
+                    return Err("Answers for a custom --input cannot be submitted.".into());
+                }
+
+                let examples = args.contains("--examples");
+
+                if examples && (input.is_some() || submit.is_some()) {
+                    return Err("--examples cannot be combined with --input or --submit.".into());
+                }
+
+                let time = args.contains("--time");
+                let bench = BenchConfig::parse(&mut args)?;
+                let format: Option<OutputFormat> = args.opt_value_from_str("--format")?;
+
+                if examples && (time || bench != BenchConfig::default() || format.is_some()) {
+                    return Err(
+                        "--examples cannot be combined with --time, --format or benchmark options."
+                            .into(),
+                    );
+                }
+
+                AppArguments::Solve {
+                    puzzles,
+                    release: args.contains("--release"),
+                    submit,
+                    input,
+                    examples,
+                    time,
+                    bench,
+                    format: format.unwrap_or_default(),
+                }
+            }
+            Some("today") => {
+                if args.contains("--year") {
+                    return Err(
+                        "today works on the puzzle of the current date and does not accept --year."
+                            .into(),
+                    );
+                }
+                AppArguments::Today
+            }
+            Some("verify") => AppArguments::Verify {
+                year: parse_year(&mut args)?,
                 release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                time: args.contains("--time"),
+                record: args.contains("--record"),
             },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
@@ -77,16 +203,74 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day } => scaffold::handle(day),
+            AppArguments::All {
+                year,
+                days,
+                release,
+                time,
+                bench,
+                stat,
+                jobs,
+                history,
+            } => all::handle(year, &days, release, time, &bench, stat, jobs, &history),
+            AppArguments::Download {
+                puzzles,
+                refresh,
+                wait,
+            } => {
+                for puzzle in puzzles {
+                    download::handle(puzzle, refresh, wait);
+                }
+            }
+            AppArguments::Read { puzzles, refresh } => {
+                for puzzle in puzzles {
+                    read::handle(puzzle, refresh);
+                }
+            }
+            AppArguments::Rollover { year } => rollover::handle(year),
+            AppArguments::Scaffold { puzzles } => {
+                for puzzle in puzzles {
+                    scaffold::handle(puzzle);
+                }
+            }
             AppArguments::Solve {
-                day,
+                puzzles,
                 release,
                 time,
+                bench,
+                format,
                 submit,
-            } => solve::handle(day, release, time, submit),
+                input,
+                examples,
+            } => {
+                if examples {
+                    let mut all_match = true;
+                    for puzzle in puzzles {
+                        all_match &= solve::handle_examples(puzzle, release);
+                    }
+                    if !all_match {
+                        std::process::exit(1);
+                    }
+                } else {
+                    for puzzle in puzzles {
+                        solve::handle(
+                            puzzle,
+                            release,
+                            time,
+                            &bench,
+                            format,
+                            submit,
+                            input.as_deref(),
+                        );
+                    }
+                }
+            }
+            AppArguments::Today => today::handle(),
+            AppArguments::Verify {
+                year,
+                release,
+                record,
+            } => verify::handle(year, release, record),
         },
     };
 }
@@ -0,0 +1,62 @@
+use std::fmt::Display;
+
+use crate::{Day, Year};
+
+/// Identifies a puzzle by its year and day.
+///
+/// The files of the default year (see [`Year::from_env`]) use the single-year layout, e.g. `src/bin/05.rs` and
+/// `data/inputs/05.txt`. Other years are namespaced: `src/bin/2022-05.rs` and `data/2022/inputs/05.txt`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    pub year: Year,
+    pub day: Day,
+}
+
+impl PuzzleId {
+    pub const fn new(year: Year, day: Day) -> Self {
+        Self { year, day }
+    }
+
+    /// The path of a data file of the puzzle, e.g. `data/inputs/05.txt` for the folder `inputs` and extension `txt`.
+    pub fn data_file(&self, folder: &str, extension: &str) -> String {
+        format!("{}/{folder}/{}.{extension}", self.year.data_dir(), self.day)
+    }
+
+    /// The name of the solution binary, e.g. `05` or `2022-05`.
+    pub fn bin_name(&self) -> String {
+        if self.year.is_default() {
+            self.day.to_string()
+        } else {
+            format!("{}-{}", self.year, self.day)
+        }
+    }
+
+    /// The path of the solution, e.g. `src/bin/05.rs` or `src/bin/2022-05.rs`.
+    pub fn bin_path(&self) -> String {
+        format!("src/bin/{}.rs", self.bin_name())
+    }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} day {}", self.year, self.day)
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::PuzzleId;
+    use crate::{day, year};
+
+    #[test]
+    fn namespaces_paths_of_other_years() {
+        // `AOC_YEAR` is set in `.cargo/config.toml`.
+        let current = PuzzleId::new(crate::Year::from_env().unwrap(), day!(5));
+        assert_eq!(current.data_file("inputs", "txt"), "data/inputs/05.txt");
+        assert_eq!(current.bin_path(), "src/bin/05.rs");
+
+        let other = PuzzleId::new(year!(2015), day!(5));
+        assert_eq!(other.data_file("inputs", "txt"), "data/2015/inputs/05.txt");
+        assert_eq!(other.bin_path(), "src/bin/2015-05.rs");
+    }
+}
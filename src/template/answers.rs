@@ -0,0 +1,201 @@
+/// Module that stores the known-good answers of solutions in `data/answers/NN.toml`.
+/// The files contain one TOML basic string per part, e.g. `part_one = "42"`.
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::{Part, PuzzleId};
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "could not parse answers: {e}"),
+            Error::IO(e) => write!(f, "could not access answers: {e}"),
+        }
+    }
+}
+
+/// The recorded answers of a day.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+impl Answers {
+    /// Returns the recorded answer of a part.
+    #[must_use]
+    pub fn get(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part_one.as_deref(),
+            Part::Two => self.part_two.as_deref(),
+        }
+    }
+
+    /// Records the answer of a part.
+    pub fn set(&mut self, part: Part, answer: String) {
+        match part {
+            Part::One => self.part_one = Some(answer),
+            Part::Two => self.part_two = Some(answer),
+        }
+    }
+
+    fn parse(s: &str) -> Result<Self, Error> {
+        let mut answers = Self::default();
+
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let error = || Error::Parser(format!("unexpected input on line {}: {line}", i + 1));
+
+            let (key, value) = line.split_once('=').ok_or_else(error)?;
+            let value = parse_string(value.trim()).ok_or_else(error)?;
+
+            match key.trim() {
+                "part_one" => answers.part_one = Some(value),
+                "part_two" => answers.part_two = Some(value),
+                _ => return Err(error()),
+            }
+        }
+
+        Ok(answers)
+    }
+
+    fn to_toml(&self) -> String {
+        [("part_one", &self.part_one), ("part_two", &self.part_two)]
+            .into_iter()
+            .filter_map(|(key, value)| {
+                Some(format!("{key} = {}\n", format_string(value.as_ref()?)))
+            })
+            .collect()
+    }
+}
+
+#[must_use]
+pub fn get_path(puzzle: PuzzleId) -> String {
+    puzzle.data_file("answers", "toml")
+}
+
+/// Loads the recorded answers of a day. A missing file is treated as no recorded answers.
+pub fn load(puzzle: PuzzleId) -> Result<Answers, Error> {
+    load_path(&get_path(puzzle))
+}
+
+/// Loads answers from a file in the format of `data/answers/NN.toml`, e.g. the expected answers of an example.
+/// A missing file is treated as no recorded answers.
+pub fn load_path(path: &str) -> Result<Answers, Error> {
+    match fs::read_to_string(path) {
+        Ok(content) => Answers::parse(&content),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Stores the answers of a day, replacing the previously recorded answers.
+pub fn store(puzzle: PuzzleId, answers: &Answers) -> Result<(), Error> {
+    let path = get_path(puzzle);
+
+    if let Some(dir) = Path::new(&path).parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    fs::write(path, answers.to_toml())?;
+    Ok(())
+}
+
+/// Parses a TOML basic string, e.g. `"a\nb"`.
+fn parse_string(s: &str) -> Option<String> {
+    let inner = s.strip_prefix('"')?.strip_suffix('"')?;
+    let mut value = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next()? {
+                'n' => value.push('\n'),
+                'r' => value.push('\r'),
+                't' => value.push('\t'),
+                '"' => value.push('"'),
+                '\\' => value.push('\\'),
+                _ => return None,
+            },
+            '"' => return None,
+            c => value.push(c),
+        }
+    }
+
+    Some(value)
+}
+
+fn format_string(s: &str) -> String {
+    let mut str = String::with_capacity(s.len() + 2);
+    str.push('"');
+
+    for c in s.chars() {
+        match c {
+            '\n' => str.push_str("\\n"),
+            '\r' => str.push_str("\\r"),
+            '\t' => str.push_str("\\t"),
+            '"' => str.push_str("\\\""),
+            '\\' => str.push_str("\\\\"),
+            c => str.push(c),
+        }
+    }
+
+    str.push('"');
+    str
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Answers;
+    use crate::Part;
+
+    #[test]
+    fn parses_answers() {
+        let answers =
+            Answers::parse("# day 1\npart_one = \"142\"\n\npart_two = \"281\"\n").unwrap();
+        assert_eq!(answers.get(Part::One), Some("142"));
+        assert_eq!(answers.get(Part::Two), Some("281"));
+    }
+
+    #[test]
+    fn parses_partial_answers() {
+        let answers = Answers::parse("part_two = \"-3\"").unwrap();
+        assert_eq!(answers.get(Part::One), None);
+        assert_eq!(answers.get(Part::Two), Some("-3"));
+    }
+
+    #[test]
+    fn round_trips_multiline_answers() {
+        let answers = Answers {
+            part_one: Some("#..#\n\"a\"\\b".into()),
+            part_two: None,
+        };
+        assert_eq!(Answers::parse(&answers.to_toml()).unwrap(), answers);
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        assert!(Answers::parse("part_one = 42").is_err());
+        assert!(Answers::parse("part_three = \"42\"").is_err());
+        assert!(Answers::parse("part_one \"42\"").is_err());
+    }
+}
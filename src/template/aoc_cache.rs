@@ -0,0 +1,66 @@
+/// Local cache of puzzle inputs and descriptions in `data/cache/<year>/<day>/`.
+/// Inputs never change, so they are only downloaded once. Descriptions are reused once they contain part two.
+use std::fs;
+use std::io;
+
+use crate::PuzzleId;
+
+#[must_use]
+pub fn get_dir(puzzle: PuzzleId) -> String {
+    format!("data/cache/{}/{}", puzzle.year, puzzle.day)
+}
+
+fn get_input_path(puzzle: PuzzleId) -> String {
+    format!("{}/input.txt", get_dir(puzzle))
+}
+
+fn get_puzzle_path(puzzle: PuzzleId) -> String {
+    format!("{}/puzzle.md", get_dir(puzzle))
+}
+
+/// Returns the cached input of a day.
+#[must_use]
+pub fn load_input(puzzle: PuzzleId) -> Option<String> {
+    fs::read_to_string(get_input_path(puzzle))
+        .ok()
+        .filter(|input| !input.is_empty())
+}
+
+/// Returns the cached puzzle description of a day if it is complete.
+#[must_use]
+pub fn load_puzzle(puzzle: PuzzleId) -> Option<String> {
+    fs::read_to_string(get_puzzle_path(puzzle))
+        .ok()
+        .filter(|description| is_complete(description))
+}
+
+pub fn store_input(puzzle: PuzzleId, input: &str) -> io::Result<()> {
+    fs::create_dir_all(get_dir(puzzle))?;
+    fs::write(get_input_path(puzzle), input)
+}
+
+pub fn store_puzzle(puzzle: PuzzleId, description: &str) -> io::Result<()> {
+    fs::create_dir_all(get_dir(puzzle))?;
+    fs::write(get_puzzle_path(puzzle), description)
+}
+
+/// The description of part two is only revealed after solving part one,
+/// so a description without it is fetched again the next time.
+fn is_complete(puzzle: &str) -> bool {
+    puzzle.contains("Part Two")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::is_complete;
+
+    #[test]
+    fn requires_part_two_to_be_complete() {
+        assert!(!is_complete(
+            "## --- Day 1: Trebuchet?! ---\n\nSomething is wrong."
+        ));
+        assert!(is_complete(
+            "## --- Day 1: Trebuchet?! ---\n\n...\n\n## --- Part Two ---\n\n..."
+        ));
+    }
+}
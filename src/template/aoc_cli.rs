@@ -1,10 +1,17 @@
-/// Wrapper module around the "aoc-cli" command-line.
+/// Wrapper module around the "aoc-cli" command-line and the built-in client, see [`Backend`].
 use std::{
+    env,
     fmt::Display,
+    fs,
+    path::Path,
     process::{Command, Output, Stdio},
+    str::FromStr,
+    time::Duration,
 };
 
-use crate::Day;
+use crate::template::aoc_cache;
+use crate::template::aoc_client::{self, Client, ClientError, Throttle};
+use crate::{Part, PuzzleId};
 
 #[derive(Debug)]
 pub enum AocCommandError {
@@ -12,106 +19,437 @@ pub enum AocCommandError {
     CommandNotCallable,
     BadExitStatus(Output),
     IoError,
+    UnrecognizedResponse(String),
+    Client(ClientError),
+    InvalidBackend(String),
+}
+
+impl AocCommandError {
+    /// Whether the error might go away by trying again, e.g. when a puzzle has just unlocked.
+    #[must_use]
+    pub fn is_transient(&self) -> bool {
+        match self {
+            AocCommandError::BadExitStatus(_) => true,
+            AocCommandError::Client(ClientError::Transport(_)) => true,
+            AocCommandError::Client(ClientError::Status(status, _)) => {
+                *status == 404 || *status == 429 || *status >= 500
+            }
+            _ => false,
+        }
+    }
+}
+
+impl From<ClientError> for AocCommandError {
+    fn from(e: ClientError) -> Self {
+        AocCommandError::Client(e)
+    }
 }
 
 impl Display for AocCommandError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            AocCommandError::CommandNotFound => write!(f, "aoc-cli is not present in environment."),
+            AocCommandError::CommandNotFound => write!(
+                f,
+                "aoc-cli is not present in environment. Configure a session token or run \"cargo install aoc-cli\" to install it."
+            ),
             AocCommandError::CommandNotCallable => write!(f, "aoc-cli could not be called."),
             AocCommandError::BadExitStatus(_) => {
                 write!(f, "aoc-cli exited with a non-zero status.")
             }
             AocCommandError::IoError => write!(f, "could not write output files to file system."),
+            AocCommandError::UnrecognizedResponse(_) => {
+                write!(f, "the response to the answer could not be recognized.")
+            }
+            AocCommandError::Client(e) => write!(f, "{e}"),
+            AocCommandError::InvalidBackend(backend) => write!(
+                f,
+                "unknown backend \"{backend}\" in \"AOC_BACKEND\", expected \"native\" or \"aoc-cli\"."
+            ),
+        }
+    }
+}
+
+/// The direction in which a wrong answer is off, if the server revealed it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+/// The server's response to a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubmitResponse {
+    Correct,
+    /// The answer is wrong. Repeated wrong answers come with an increasing wait.
+    Incorrect {
+        hint: Option<Hint>,
+        wait: Option<Duration>,
+    },
+    /// The answer was not checked because it was submitted too soon after the previous one.
+    RateLimited {
+        wait: Option<Duration>,
+    },
+    AlreadySolved,
+    /// The part can not be submitted yet, e.g. part two before part one is solved.
+    WrongLevel,
+}
+
+impl SubmitResponse {
+    /// Classifies the text of the server's response to an answer for a part.
+    ///
+    /// The server responds with the same message for a puzzle that is already complete and for a part that is not
+    /// unlocked yet. As part one is always unlocked, it is classified as [`SubmitResponse::AlreadySolved`] for
+    /// part one and as [`SubmitResponse::WrongLevel`] for part two.
+    #[must_use]
+    pub fn parse(part: Part, response: &str) -> Option<Self> {
+        if response.contains("That's the right answer") {
+            Some(SubmitResponse::Correct)
+        } else if response.contains("That's not the right answer") {
+            let hint = if response.contains("too high") {
+                Some(Hint::TooHigh)
+            } else if response.contains("too low") {
+                Some(Hint::TooLow)
+            } else {
+                None
+            };
+
+            Some(SubmitResponse::Incorrect {
+                hint,
+                wait: parse_wait(response),
+            })
+        } else if response.contains("You gave an answer too recently") {
+            Some(SubmitResponse::RateLimited {
+                wait: parse_wait(response),
+            })
+        } else if response.contains("You don't seem to be solving the right level") {
+            Some(if part == Part::One {
+                SubmitResponse::AlreadySolved
+            } else {
+                SubmitResponse::WrongLevel
+            })
+        } else {
+            None
+        }
+    }
+
+    /// The time the server asked to wait before submitting again.
+    #[must_use]
+    pub fn wait(&self) -> Option<Duration> {
+        match self {
+            SubmitResponse::Incorrect { wait, .. } | SubmitResponse::RateLimited { wait } => *wait,
+            _ => None,
+        }
+    }
+
+    /// Whether the server rejected the answer as wrong.
+    #[must_use]
+    pub fn is_incorrect(&self) -> bool {
+        matches!(self, SubmitResponse::Incorrect { .. })
+    }
+}
+
+impl Display for SubmitResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            SubmitResponse::Correct => "correct",
+            SubmitResponse::Incorrect {
+                hint: Some(Hint::TooHigh),
+                ..
+            } => "too high",
+            SubmitResponse::Incorrect {
+                hint: Some(Hint::TooLow),
+                ..
+            } => "too low",
+            SubmitResponse::Incorrect { hint: None, .. } => "incorrect",
+            SubmitResponse::RateLimited { .. } => "rate limited",
+            SubmitResponse::AlreadySolved => "already solved",
+            SubmitResponse::WrongLevel => "wrong level",
+        })
+    }
+}
+
+/// Extracts the time the server asks to wait before the next submission, e.g. from
+/// "You have 1m 30s left to wait." or "Please wait one minute before trying again.".
+fn parse_wait(response: &str) -> Option<Duration> {
+    if let Some((before, _)) = response.split_once(" left to wait") {
+        let time = before.rsplit("You have ").next()?;
+
+        return time
+            .split_whitespace()
+            .map(|unit| {
+                let (value, factor) = if let Some(value) = unit.strip_suffix('h') {
+                    (value, 3600)
+                } else if let Some(value) = unit.strip_suffix('m') {
+                    (value, 60)
+                } else {
+                    (unit.strip_suffix('s')?, 1)
+                };
+                value.parse::<u64>().ok().map(|value| value * factor)
+            })
+            .sum::<Option<u64>>()
+            .map(Duration::from_secs);
+    }
+
+    let (_, after) = response.split_once("wait ")?;
+    let mut words = after.split_whitespace();
+
+    let amount = match words.next()? {
+        "one" | "a" => 1,
+        "two" => 2,
+        "five" => 5,
+        "ten" => 10,
+        n => n.parse().ok()?,
+    };
+
+    let factor = match words.next()? {
+        unit if unit.starts_with("minute") => 60,
+        unit if unit.starts_with("second") => 1,
+        _ => return None,
+    };
+
+    Some(Duration::from_secs(amount * factor))
+}
+
+/// The way the Advent of Code website is accessed.
+pub enum Backend {
+    /// The built-in HTTP client.
+    Native(Client),
+    /// The external `aoc` executable.
+    AocCli,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BackendKind {
+    Native,
+    AocCli,
+}
+
+impl FromStr for BackendKind {
+    type Err = AocCommandError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "native" => Ok(BackendKind::Native),
+            "aoc-cli" => Ok(BackendKind::AocCli),
+            _ => Err(AocCommandError::InvalidBackend(s.into())),
         }
     }
 }
 
+impl Backend {
+    /// Selects the backend set in `AOC_BACKEND` (`native` or `aoc-cli`). Without it, the built-in client is used
+    /// if a session token is configured and aoc-cli otherwise.
+    pub fn from_env() -> Result<Self, AocCommandError> {
+        let kind = match env::var("AOC_BACKEND") {
+            Ok(backend) => backend.parse()?,
+            Err(_) if aoc_client::find_session().is_some() => BackendKind::Native,
+            Err(_) => BackendKind::AocCli,
+        };
+
+        match kind {
+            BackendKind::Native => Ok(Backend::Native(Client::from_env()?)),
+            BackendKind::AocCli => {
+                Command::new("aoc")
+                    .arg("-V")
+                    .output()
+                    .map_err(|_| AocCommandError::CommandNotFound)?;
+                Ok(Backend::AocCli)
+            }
+        }
+    }
+}
+
+/// Checks that the Advent of Code website can be accessed by any backend.
 pub fn check() -> Result<(), AocCommandError> {
-    Command::new("aoc")
-        .arg("-V")
-        .output()
-        .map_err(|_| AocCommandError::CommandNotFound)?;
-    Ok(())
+    Backend::from_env().map(|_| ())
 }
 
-pub fn read(day: Day) -> Result<Output, AocCommandError> {
-    let puzzle_path = get_puzzle_path(day);
+/// Prints the puzzle description and writes it to `data/puzzles`.
+/// A complete description is taken from the cache unless `refresh` is set.
+pub fn read(puzzle: PuzzleId, refresh: bool) -> Result<(), AocCommandError> {
+    let puzzle_path = get_puzzle_path(puzzle);
 
-    let args = build_args(
-        "read",
-        &[
-            "--description-only".into(),
-            "--puzzle-file".into(),
-            puzzle_path,
-        ],
-        day,
-    );
+    if let Some(description) = Some(puzzle)
+        .filter(|_| !refresh)
+        .and_then(aoc_cache::load_puzzle)
+    {
+        write_file(&puzzle_path, &description)?;
+        println!("{description}");
+        return Ok(());
+    }
+
+    match Backend::from_env()? {
+        Backend::Native(client) => {
+            let description = client.puzzle(puzzle)?;
+            write_file(&puzzle_path, &description)?;
+            println!("{description}");
+        }
+        Backend::AocCli => {
+            let args = build_args(
+                "read",
+                &[
+                    "--description-only".into(),
+                    "--puzzle-file".into(),
+                    puzzle_path.clone(),
+                ],
+                puzzle,
+            );
 
-    call_aoc_cli(&args)
+            call_aoc_cli(&args)?;
+        }
+    }
+
+    update_cache(puzzle, None, &puzzle_path);
+    Ok(())
 }
 
-pub fn download(day: Day) -> Result<Output, AocCommandError> {
-    let input_path = get_input_path(day);
-    let puzzle_path = get_puzzle_path(day);
+/// Writes the input and puzzle description to `data/inputs` and `data/puzzles`.
+/// Both are taken from the cache unless `refresh` is set.
+pub fn download(puzzle: PuzzleId, refresh: bool) -> Result<(), AocCommandError> {
+    let input_path = get_input_path(puzzle);
+    let puzzle_path = get_puzzle_path(puzzle);
 
-    let args = build_args(
-        "download",
-        &[
-            "--overwrite".into(),
-            "--input-file".into(),
-            input_path.to_string(),
-            "--puzzle-file".into(),
-            puzzle_path.to_string(),
-        ],
-        day,
-    );
+    let cached_input = Some(puzzle)
+        .filter(|_| !refresh)
+        .and_then(aoc_cache::load_input);
+    let cached_puzzle = Some(puzzle)
+        .filter(|_| !refresh)
+        .and_then(aoc_cache::load_puzzle);
+
+    if let (Some(input), Some(description)) = (&cached_input, &cached_puzzle) {
+        write_file(&input_path, input)?;
+        write_file(&puzzle_path, description)?;
+        println!("🎄 Using the cached input and puzzle, pass --refresh to download them again.");
+    } else {
+        match Backend::from_env()? {
+            Backend::Native(client) => {
+                let input = match cached_input {
+                    Some(input) => input,
+                    None => client.input(puzzle)?,
+                };
+                write_file(&input_path, &input)?;
+
+                let description = match cached_puzzle {
+                    Some(description) => description,
+                    None => client.puzzle(puzzle)?,
+                };
+                write_file(&puzzle_path, &description)?;
+            }
+            Backend::AocCli => {
+                let args = build_args(
+                    "download",
+                    &[
+                        "--overwrite".into(),
+                        "--input-file".into(),
+                        input_path.to_string(),
+                        "--puzzle-file".into(),
+                        puzzle_path.to_string(),
+                    ],
+                    puzzle,
+                );
+
+                call_aoc_cli(&args)?;
+            }
+        }
+
+        update_cache(puzzle, Some(&input_path), &puzzle_path);
+    }
 
-    let output = call_aoc_cli(&args)?;
     println!("---");
     println!("🎄 Successfully wrote input to \"{}\".", &input_path);
     println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
-    Ok(output)
+    Ok(())
 }
 
-pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
-    // workaround: the argument order is inverted for submit.
-    let mut args = build_args("submit", &[], day);
-    args.push(part.to_string());
-    args.push(result.to_string());
-    call_aoc_cli(&args)
+/// Stores the files that were just written by a backend in the cache.
+/// A failure to do so is not fatal, the files are downloaded again next time.
+fn update_cache(puzzle: PuzzleId, input_path: Option<&str>, puzzle_path: &str) {
+    let result = input_path
+        .map_or(Ok(()), |path| {
+            fs::read_to_string(path).and_then(|input| aoc_cache::store_input(puzzle, &input))
+        })
+        .and_then(|()| {
+            fs::read_to_string(puzzle_path)
+                .and_then(|description| aoc_cache::store_puzzle(puzzle, &description))
+        });
+
+    if let Err(e) = result {
+        eprintln!(
+            "Failed to update the cache in \"{}\": {e}",
+            aoc_cache::get_dir(puzzle)
+        );
+    }
 }
 
-fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+pub fn submit(
+    puzzle: PuzzleId,
+    part: Part,
+    result: &str,
+) -> Result<SubmitResponse, AocCommandError> {
+    submit_with(&Backend::from_env()?, puzzle, part, result)
 }
 
-fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
+/// Submits an answer with the given backend, see [`submit`].
+pub fn submit_with(
+    backend: &Backend,
+    puzzle: PuzzleId,
+    part: Part,
+    result: &str,
+) -> Result<SubmitResponse, AocCommandError> {
+    let response = match backend {
+        Backend::Native(client) => {
+            let response = client.submit(puzzle, part, result)?;
+            println!("{response}");
+            response
+        }
+        Backend::AocCli => {
+            // workaround: the argument order is inverted for submit.
+            let mut args = build_args("submit", &[], puzzle);
+            args.push(part.to_string());
+            args.push(result.to_string());
+
+            let output = call_aoc_cli_captured(&args)?;
+            String::from_utf8_lossy(&output.stdout).into_owned()
+        }
+    };
+
+    SubmitResponse::parse(part, &response).ok_or(AocCommandError::UnrecognizedResponse(response))
 }
 
-fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
-    }
+fn get_input_path(puzzle: PuzzleId) -> String {
+    puzzle.data_file("inputs", "txt")
 }
 
-fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
-    let mut cmd_args = args.to_vec();
+fn get_puzzle_path(puzzle: PuzzleId) -> String {
+    puzzle.data_file("puzzles", "md")
+}
 
-    if let Some(year) = get_year() {
-        cmd_args.push("--year".into());
-        cmd_args.push(year.to_string());
+fn write_file(path: &str, content: &str) -> Result<(), AocCommandError> {
+    if let Some(dir) = Path::new(path).parent() {
+        fs::create_dir_all(dir).map_err(|_| AocCommandError::IoError)?;
     }
 
-    cmd_args.append(&mut vec!["--day".into(), day.to_string(), command.into()]);
+    fs::write(path, content).map_err(|_| AocCommandError::IoError)
+}
+
+fn build_args(command: &str, args: &[String], puzzle: PuzzleId) -> Vec<String> {
+    let mut cmd_args = args.to_vec();
+
+    cmd_args.append(&mut vec![
+        "--year".into(),
+        puzzle.year.to_string(),
+        "--day".into(),
+        puzzle.day.to_string(),
+        command.into(),
+    ]);
 
     cmd_args
 }
 
 fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
     // println!("Calling >aoc with: {}", args.join(" "));
+    Throttle::from_env().wait();
+
     let output = Command::new("aoc")
         .args(args)
         .stdout(Stdio::inherit())
@@ -125,3 +463,109 @@ fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
         Err(AocCommandError::BadExitStatus(output))
     }
 }
+
+/// Calls aoc-cli with its standard output captured, so the response can be inspected.
+/// The captured output is still echoed to the terminal.
+fn call_aoc_cli_captured(args: &[String]) -> Result<Output, AocCommandError> {
+    Throttle::from_env().wait();
+
+    let output = Command::new("aoc")
+        .args(args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|_| AocCommandError::CommandNotCallable)?;
+
+    print!("{}", String::from_utf8_lossy(&output.stdout));
+
+    if output.status.success() {
+        Ok(output)
+    } else {
+        Err(AocCommandError::BadExitStatus(output))
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_wait, Hint, SubmitResponse};
+    use crate::Part;
+    use std::time::Duration;
+
+    #[test]
+    fn classifies_responses() {
+        assert_eq!(
+            SubmitResponse::parse(
+                Part::One,
+                "That's the right answer! You are one gold star closer."
+            ),
+            Some(SubmitResponse::Correct)
+        );
+        assert_eq!(
+            SubmitResponse::parse(
+                Part::One,
+                "That's not the right answer; your answer is too high. Please wait one minute before trying again."
+            ),
+            Some(SubmitResponse::Incorrect {
+                hint: Some(Hint::TooHigh),
+                wait: Some(Duration::from_secs(60))
+            })
+        );
+        assert_eq!(
+            SubmitResponse::parse(
+                Part::Two,
+                "That's not the right answer; your answer is too low."
+            ),
+            Some(SubmitResponse::Incorrect {
+                hint: Some(Hint::TooLow),
+                wait: None
+            })
+        );
+        assert_eq!(
+            SubmitResponse::parse(
+                Part::One,
+                "That's not the right answer. If you're stuck, ..."
+            ),
+            Some(SubmitResponse::Incorrect {
+                hint: None,
+                wait: None
+            })
+        );
+        assert_eq!(
+            SubmitResponse::parse(
+                Part::One,
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 43s left to wait."
+            ),
+            Some(SubmitResponse::RateLimited {
+                wait: Some(Duration::from_secs(43))
+            })
+        );
+        assert_eq!(SubmitResponse::parse(Part::One, "???"), None);
+    }
+
+    #[test]
+    fn classifies_wrong_level_responses_by_part() {
+        let response =
+            "You don't seem to be solving the right level.  Did you already complete it?";
+        assert_eq!(
+            SubmitResponse::parse(Part::One, response),
+            Some(SubmitResponse::AlreadySolved)
+        );
+        assert_eq!(
+            SubmitResponse::parse(Part::Two, response),
+            Some(SubmitResponse::WrongLevel)
+        );
+    }
+
+    #[test]
+    fn parses_waits() {
+        assert_eq!(
+            parse_wait("You have 1m 30s left to wait."),
+            Some(Duration::from_secs(90))
+        );
+        assert_eq!(
+            parse_wait("please wait 5 minutes before trying again."),
+            Some(Duration::from_secs(300))
+        );
+        assert_eq!(parse_wait("That's the right answer!"), None);
+    }
+}
@@ -0,0 +1,389 @@
+/// Built-in HTTP client for the Advent of Code website, used instead of aoc-cli when a session token is configured.
+use std::fmt::Display;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::{env, fs, thread};
+
+use crate::{Part, PuzzleId};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Identifies the requests as coming from this repository, can be overridden with `AOC_USER_AGENT`.
+pub const DEFAULT_USER_AGENT: &str = "github.com/ricohageman/advent-of-code-2023";
+
+pub const DEFAULT_REQUEST_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Stores the time of the last request, so the interval is kept across processes.
+const LAST_REQUEST_PATH: &str = "data/cache/last-request";
+
+/// Width of the text that puzzle descriptions and responses are converted to.
+const TEXT_WIDTH: usize = 80;
+
+#[derive(Debug)]
+pub enum ClientError {
+    MissingSession,
+    /// The server responded with a non-success status code.
+    Status(u16, String),
+    /// The request could not be sent or the response could not be read.
+    Transport(String),
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::MissingSession => write!(
+                f,
+                "no session token found in \"AOC_SESSION\" or \"~/.adventofcode.session\"."
+            ),
+            ClientError::Status(status, body) => {
+                write!(
+                    f,
+                    "the server responded with status {status}: {}",
+                    body.trim()
+                )
+            }
+            ClientError::Transport(e) => write!(f, "request failed: {e}"),
+        }
+    }
+}
+
+impl From<ureq::Error> for ClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(status, response) => {
+                ClientError::Status(status, response.into_string().unwrap_or_default())
+            }
+            ureq::Error::Transport(e) => ClientError::Transport(e.to_string()),
+        }
+    }
+}
+
+impl From<std::io::Error> for ClientError {
+    fn from(e: std::io::Error) -> Self {
+        ClientError::Transport(e.to_string())
+    }
+}
+
+/// Reads the session token from the `AOC_SESSION` environment variable, or from the file at `AOC_SESSION_FILE`
+/// which defaults to `~/.adventofcode.session`, the location aoc-cli uses as well.
+#[must_use]
+pub fn find_session() -> Option<String> {
+    if let Ok(session) = env::var("AOC_SESSION") {
+        return Some(session.trim().to_string()).filter(|s| !s.is_empty());
+    }
+
+    let path = env::var("AOC_SESSION_FILE")
+        .or_else(|_| env::var("HOME").map(|home| format!("{home}/.adventofcode.session")))
+        .ok()?;
+
+    fs::read_to_string(path)
+        .ok()
+        .map(|session| session.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// Keeps a minimum interval between requests to the website, including those made by aoc-cli.
+pub struct Throttle {
+    path: String,
+    interval: Duration,
+}
+
+impl Throttle {
+    /// Reads the interval in seconds from `AOC_REQUEST_INTERVAL`, which defaults to [`DEFAULT_REQUEST_INTERVAL`].
+    #[must_use]
+    pub fn from_env() -> Self {
+        let interval = env::var("AOC_REQUEST_INTERVAL")
+            .ok()
+            .and_then(|x| x.parse::<f64>().ok())
+            .and_then(|x| Duration::try_from_secs_f64(x).ok())
+            .unwrap_or(DEFAULT_REQUEST_INTERVAL);
+
+        Self {
+            path: LAST_REQUEST_PATH.into(),
+            interval,
+        }
+    }
+
+    /// Sleeps until the interval since the last request has passed, then records the current request.
+    pub fn wait(&self) {
+        let last = fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|x| x.trim().parse::<u64>().ok())
+            .map(Duration::from_millis);
+
+        let remaining = remaining_wait(last, now(), self.interval);
+
+        if !remaining.is_zero() {
+            eprintln!(
+                "Waiting {:.1}s before the next request to Advent of Code...",
+                remaining.as_secs_f64()
+            );
+            thread::sleep(remaining);
+        }
+
+        if let Some(dir) = Path::new(&self.path).parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+
+        // failing to record the request only weakens the throttle for the next process.
+        let _ = fs::write(&self.path, now().as_millis().to_string());
+    }
+}
+
+/// The time since the unix epoch.
+fn now() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+/// The time to wait before the next request, given the time of the last request since the unix epoch.
+fn remaining_wait(last: Option<Duration>, now: Duration, interval: Duration) -> Duration {
+    last.map_or(Duration::ZERO, |last| {
+        (last + interval).saturating_sub(now).min(interval)
+    })
+}
+
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+    throttle: Option<Throttle>,
+}
+
+impl Client {
+    #[must_use]
+    pub fn new(base_url: &str, session: String, user_agent: &str) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(user_agent)
+            .timeout(Duration::from_secs(30))
+            .build();
+
+        Self {
+            agent,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session,
+            throttle: None,
+        }
+    }
+
+    /// Keeps a minimum interval between the requests of this client.
+    #[must_use]
+    pub fn with_throttle(mut self, throttle: Throttle) -> Self {
+        self.throttle = Some(throttle);
+        self
+    }
+
+    /// Creates a throttled client with the session found by [`find_session`].
+    /// The base URL can be overridden with `AOC_BASE_URL`, e.g. to point it to a local mock server,
+    /// and the User-Agent with `AOC_USER_AGENT`.
+    pub fn from_env() -> Result<Self, ClientError> {
+        let session = find_session().ok_or(ClientError::MissingSession)?;
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        let user_agent = env::var("AOC_USER_AGENT").unwrap_or_else(|_| DEFAULT_USER_AGENT.into());
+        Ok(Self::new(&base_url, session, &user_agent).with_throttle(Throttle::from_env()))
+    }
+
+    /// Fetches the puzzle description of a day as text.
+    pub fn puzzle(&self, puzzle: PuzzleId) -> Result<String, ClientError> {
+        let html = self.get(&puzzle_path(puzzle))?;
+        Ok(articles_to_text(&html))
+    }
+
+    /// Fetches the personal puzzle input of a day.
+    pub fn input(&self, puzzle: PuzzleId) -> Result<String, ClientError> {
+        self.get(&format!("{}/input", puzzle_path(puzzle)))
+    }
+
+    /// Submits an answer and returns the server's response as text.
+    pub fn submit(
+        &self,
+        puzzle: PuzzleId,
+        part: Part,
+        answer: &str,
+    ) -> Result<String, ClientError> {
+        let url = format!("{}/{}/answer", self.base_url, puzzle_path(puzzle));
+        self.throttle();
+
+        let html = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?
+            .into_string()?;
+
+        Ok(articles_to_text(&html))
+    }
+
+    fn get(&self, path: &str) -> Result<String, ClientError> {
+        self.throttle();
+
+        Ok(self
+            .agent
+            .get(&format!("{}/{path}", self.base_url))
+            .set("Cookie", &format!("session={}", self.session))
+            .call()?
+            .into_string()?)
+    }
+
+    fn throttle(&self) {
+        if let Some(throttle) = &self.throttle {
+            throttle.wait();
+        }
+    }
+}
+
+/// The path of a puzzle on the website, e.g. `2023/day/5`.
+fn puzzle_path(puzzle: PuzzleId) -> String {
+    format!("{}/day/{}", puzzle.year, puzzle.day.into_inner())
+}
+
+/// Converts the `<article>` elements of a page to text, which contain the puzzle description or the response to
+/// an answer. Falls back to the whole page if it does not contain any.
+fn articles_to_text(html: &str) -> String {
+    let mut articles = String::new();
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let Some(end) = rest[start..].find("</article>") else {
+            break;
+        };
+        let end = start + end + "</article>".len();
+        articles.push_str(&rest[start..end]);
+        rest = &rest[end..];
+    }
+
+    let html = if articles.is_empty() { html } else { &articles };
+    html2text::from_read(html.as_bytes(), TEXT_WIDTH)
+}
+
+#[cfg(feature = "test_lib")]
+pub(crate) mod tests {
+    use super::{remaining_wait, Client, ClientError};
+    use crate::{day, year, Part, PuzzleId};
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::Duration;
+
+    /// Serves a single request with the given status and body, and returns the URL of the server
+    /// together with a receiver for the raw request.
+    pub(crate) fn mock_server(status: u16, body: &'static str) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request = String::new();
+            let mut content_length = 0;
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = value.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut content = vec![0; content_length];
+            reader.read_exact(&mut content).unwrap();
+            request.push_str(&String::from_utf8(content).unwrap());
+
+            let mut stream = stream;
+            write!(
+                stream,
+                "HTTP/1.1 {status} OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+
+            tx.send(request).unwrap();
+        });
+
+        (url, rx)
+    }
+
+    #[test]
+    fn fetches_input_with_session() {
+        let (url, requests) = mock_server(200, "1\n2\n3\n");
+        let client = Client::new(&url, "secret".into(), "test-agent");
+
+        assert_eq!(
+            client.input(PuzzleId::new(year!(2023), day!(5))).unwrap(),
+            "1\n2\n3\n"
+        );
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("GET /2023/day/5/input HTTP/1.1"));
+        assert!(request.contains("Cookie: session=secret"));
+        assert!(request.contains("User-Agent: test-agent"));
+    }
+
+    #[test]
+    fn extracts_puzzle_description() {
+        let (url, _) = mock_server(
+            200,
+            "<html><nav>menu</nav><main><article class=\"day-desc\"><h2>--- Day 1: Trebuchet?! ---</h2><p>Something is wrong.</p></article></main></html>",
+        );
+        let client = Client::new(&url, "secret".into(), "test-agent");
+
+        let puzzle = client.puzzle(PuzzleId::new(year!(2023), day!(1))).unwrap();
+        assert!(puzzle.contains("Day 1: Trebuchet?!"));
+        assert!(puzzle.contains("Something is wrong."));
+        assert!(!puzzle.contains("menu"));
+    }
+
+    #[test]
+    fn submits_answer_as_form() {
+        let (url, requests) = mock_server(
+            200,
+            "<main><article><p>That's the right answer! You are one gold star closer.</p></article></main>",
+        );
+        let client = Client::new(&url, "secret".into(), "test-agent");
+
+        let response = client
+            .submit(PuzzleId::new(year!(2023), day!(12)), Part::Two, "42")
+            .unwrap();
+        assert!(response.contains("That's the right answer"));
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("POST /2023/day/12/answer HTTP/1.1"));
+        assert!(request.ends_with("level=2&answer=42"));
+    }
+
+    #[test]
+    fn reports_error_status() {
+        let (url, _) = mock_server(
+            404,
+            "Please don't repeatedly request this endpoint before it unlocks!",
+        );
+        let client = Client::new(&url, "secret".into(), "test-agent");
+
+        assert!(matches!(
+            client.input(PuzzleId::new(year!(2023), day!(25))),
+            Err(ClientError::Status(404, _))
+        ));
+    }
+
+    #[test]
+    fn waits_for_remaining_interval() {
+        let interval = Duration::from_secs(5);
+        let at = Duration::from_secs;
+
+        assert_eq!(remaining_wait(None, at(100), interval), Duration::ZERO);
+        assert_eq!(remaining_wait(Some(at(98)), at(100), interval), at(3));
+        assert_eq!(
+            remaining_wait(Some(at(90)), at(100), interval),
+            Duration::ZERO
+        );
+        // a last request in the future, e.g. after adjusting the clock, never blocks longer than the interval.
+        assert_eq!(remaining_wait(Some(at(200)), at(100), interval), interval);
+    }
+}
@@ -0,0 +1,455 @@
+/// Module that keeps a history of benchmark runs and detects regressions between them.
+/// Every timed run of `all` is appended as a single JSON line to `data/benchmarks.jsonl`, see [`get_path`].
+use std::error;
+use std::fmt::Display;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::Command;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use tinyjson::JsonValue;
+
+use crate::template::record::{PartRecord, PartStatus};
+use crate::template::runner::Statistic;
+use crate::{Day, Part, Year};
+
+/// The path of the benchmark history of a year, e.g. `data/benchmarks.jsonl` for the default year.
+#[must_use]
+pub fn get_path(year: Year) -> String {
+    format!("{}/benchmarks.jsonl", year.data_dir())
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "could not parse benchmark history: {e}"),
+            Error::IO(e) => write!(f, "could not access benchmark history: {e}"),
+        }
+    }
+}
+
+/// The run that the current benchmark is compared against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Reference {
+    /// The most recent run with the same build profile.
+    #[default]
+    Previous,
+    /// The most recent run with the same build profile that was pinned as baseline.
+    Baseline,
+}
+
+impl FromStr for Reference {
+    type Err = ReferenceFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "previous" => Ok(Self::Previous),
+            "baseline" => Ok(Self::Baseline),
+            _ => Err(ReferenceFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`Reference`].
+#[derive(Debug)]
+pub struct ReferenceFromStrError;
+
+impl error::Error for ReferenceFromStrError {}
+
+impl Display for ReferenceFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting one of `previous` or `baseline`")
+    }
+}
+
+/// Controls how a benchmark run is stored and compared to earlier runs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HistoryOptions {
+    /// The run to compare against.
+    pub compare: Reference,
+    /// Slowdown in percent above which a part is flagged as regressed.
+    pub threshold: f64,
+    /// Whether the current run is stored as the new baseline.
+    pub pin_baseline: bool,
+}
+
+impl Default for HistoryOptions {
+    fn default() -> Self {
+        Self {
+            compare: Reference::default(),
+            threshold: 10.0,
+            pin_baseline: false,
+        }
+    }
+}
+
+impl HistoryOptions {
+    /// Consumes the history options (`--compare <previous|baseline>`, `--threshold <percent>` and
+    /// `--pin-baseline`) from the arguments.
+    pub fn parse(args: &mut pico_args::Arguments) -> Result<Self, Box<dyn error::Error>> {
+        let default = Self::default();
+
+        let options = Self {
+            compare: args
+                .opt_value_from_str("--compare")?
+                .unwrap_or(default.compare),
+            threshold: args
+                .opt_value_from_str("--threshold")?
+                .unwrap_or(default.threshold),
+            pin_baseline: args.contains("--pin-baseline"),
+        };
+
+        if !options.threshold.is_finite() || options.threshold < 0.0 {
+            return Err("--threshold expects a non-negative percentage".into());
+        }
+
+        Ok(options)
+    }
+}
+
+/// The timing of a single solution part within a run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PartTiming {
+    pub day: Day,
+    pub part: Part,
+    pub mean: Duration,
+    pub median: Duration,
+}
+
+impl PartTiming {
+    fn get(&self, statistic: Statistic) -> Duration {
+        match statistic {
+            Statistic::Mean => self.mean,
+            Statistic::Median => self.median,
+        }
+    }
+}
+
+/// A single benchmark run of all solutions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Run {
+    /// The git revision the run was made from, suffixed with `-dirty` for uncommitted changes.
+    pub revision: String,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// Whether the solutions were compiled in release mode.
+    pub release: bool,
+    /// Whether this run is pinned as baseline.
+    pub baseline: bool,
+    pub parts: Vec<PartTiming>,
+}
+
+impl Run {
+    /// Creates a run for the current revision and time from the records of solved parts.
+    #[must_use]
+    pub fn new(records: &[PartRecord], release: bool, baseline: bool) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        Self {
+            revision: git_revision().unwrap_or_else(|| "unknown".into()),
+            timestamp,
+            release,
+            baseline,
+            parts: records
+                .iter()
+                .filter(|record| record.status == PartStatus::Solved)
+                .map(|record| PartTiming {
+                    day: record.day,
+                    part: record.part,
+                    mean: record.stats.mean,
+                    median: record.stats.median,
+                })
+                .collect(),
+        }
+    }
+
+    fn to_json(&self) -> String {
+        #[allow(clippy::cast_precision_loss)]
+        let number = |n: u128| JsonValue::Number(n as f64);
+
+        let parts = self
+            .parts
+            .iter()
+            .map(|part| {
+                JsonValue::Object(
+                    [
+                        ("day", number(u128::from(part.day.into_inner()))),
+                        ("part", number(u128::from(part.part.into_inner()))),
+                        ("mean_nanos", number(part.mean.as_nanos())),
+                        ("median_nanos", number(part.median.as_nanos())),
+                    ]
+                    .into_iter()
+                    .map(|(key, value)| (key.to_string(), value))
+                    .collect(),
+                )
+            })
+            .collect();
+
+        JsonValue::Object(
+            [
+                ("revision", JsonValue::String(self.revision.clone())),
+                ("timestamp", number(u128::from(self.timestamp))),
+                ("release", JsonValue::Boolean(self.release)),
+                ("baseline", JsonValue::Boolean(self.baseline)),
+                ("parts", JsonValue::Array(parts)),
+            ]
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), value))
+            .collect(),
+        )
+        .stringify()
+        .expect("runs only contain finite numbers")
+    }
+
+    fn from_json(line: &str) -> Option<Self> {
+        let JsonValue::Object(fields) = line.parse::<JsonValue>().ok()? else {
+            return None;
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let number = |value: &JsonValue| match value {
+            JsonValue::Number(n) => Some(*n as u64),
+            _ => None,
+        };
+
+        let boolean = |value: &JsonValue| match value {
+            JsonValue::Boolean(b) => Some(*b),
+            _ => None,
+        };
+
+        let JsonValue::String(revision) = fields.get("revision")? else {
+            return None;
+        };
+
+        let JsonValue::Array(parts) = fields.get("parts")? else {
+            return None;
+        };
+
+        let parts = parts
+            .iter()
+            .map(|part| {
+                let JsonValue::Object(part) = part else {
+                    return None;
+                };
+
+                Some(PartTiming {
+                    day: Day::new(u8::try_from(number(part.get("day")?)?).ok()?)?,
+                    part: Part::new(u8::try_from(number(part.get("part")?)?).ok()?)?,
+                    mean: Duration::from_nanos(number(part.get("mean_nanos")?)?),
+                    median: Duration::from_nanos(number(part.get("median_nanos")?)?),
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(Self {
+            revision: revision.clone(),
+            timestamp: number(fields.get("timestamp")?)?,
+            release: boolean(fields.get("release")?)?,
+            baseline: boolean(fields.get("baseline")?)?,
+            parts,
+        })
+    }
+}
+
+/// A part that got slower compared to the reference run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Regression {
+    pub day: Day,
+    pub part: Part,
+    pub before: Duration,
+    pub after: Duration,
+}
+
+impl Regression {
+    /// The slowdown in percent.
+    #[must_use]
+    pub fn percent(&self) -> f64 {
+        (self.after.as_secs_f64() / self.before.as_secs_f64() - 1.0) * 100.0
+    }
+}
+
+impl Display for Regression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Day {} Part {}: {:.1?} -> {:.1?} (+{:.1}%)",
+            self.day,
+            self.part,
+            self.before,
+            self.after,
+            self.percent()
+        )
+    }
+}
+
+/// Returns the run that a new run with the given build profile is compared against.
+#[must_use]
+pub fn find_reference(runs: &[Run], release: bool, reference: Reference) -> Option<&Run> {
+    runs.iter()
+        .rev()
+        .filter(|run| run.release == release)
+        .find(|run| match reference {
+            Reference::Previous => true,
+            Reference::Baseline => run.baseline,
+        })
+}
+
+/// Returns all parts of `current` that are slower than in `reference` by more than `threshold` percent.
+/// Parts that are missing from either run are ignored.
+#[must_use]
+pub fn find_regressions(
+    reference: &Run,
+    current: &Run,
+    statistic: Statistic,
+    threshold: f64,
+) -> Vec<Regression> {
+    current
+        .parts
+        .iter()
+        .filter_map(|part| {
+            let before = reference
+                .parts
+                .iter()
+                .find(|p| p.day == part.day && p.part == part.part)?;
+
+            let regression = Regression {
+                day: part.day,
+                part: part.part,
+                before: before.get(statistic),
+                after: part.get(statistic),
+            };
+
+            (!regression.before.is_zero() && regression.percent() > threshold).then_some(regression)
+        })
+        .collect()
+}
+
+/// Reads all runs from the history file. A missing file is treated as an empty history.
+pub fn load(path: &str) -> Result<Vec<Run>, Error> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e.into()),
+    };
+
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .enumerate()
+        .map(|(i, line)| {
+            Run::from_json(line).ok_or_else(|| Error::Parser(format!("line {}", i + 1)))
+        })
+        .collect()
+}
+
+/// Appends a run to the history file.
+pub fn append(path: &str, run: &Run) -> Result<(), Error> {
+    if let Some(dir) = Path::new(path).parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", run.to_json())?;
+    Ok(())
+}
+
+fn git_revision() -> Option<String> {
+    let output = Command::new("git")
+        .args(["describe", "--always", "--dirty"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{find_reference, find_regressions, PartTiming, Reference, Run};
+    use crate::template::runner::Statistic;
+    use crate::{day, Part};
+    use std::time::Duration;
+
+    fn run(revision: &str, release: bool, baseline: bool, nanos: &[(u64, u64)]) -> Run {
+        Run {
+            revision: revision.into(),
+            timestamp: 1_700_000_000,
+            release,
+            baseline,
+            parts: nanos
+                .iter()
+                .enumerate()
+                .map(|(i, (mean, median))| PartTiming {
+                    day: day!(1),
+                    part: Part::new(u8::try_from(i + 1).unwrap()).unwrap(),
+                    mean: Duration::from_nanos(*mean),
+                    median: Duration::from_nanos(*median),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let run = run("abc1234-dirty", true, false, &[(100, 90), (2000, 1900)]);
+        assert_eq!(Run::from_json(&run.to_json()), Some(run));
+    }
+
+    #[test]
+    fn finds_reference_with_same_profile() {
+        let runs = vec![
+            run("a", true, true, &[]),
+            run("b", true, false, &[]),
+            run("c", false, false, &[]),
+        ];
+
+        let previous = find_reference(&runs, true, Reference::Previous).unwrap();
+        assert_eq!(previous.revision, "b");
+
+        let baseline = find_reference(&runs, true, Reference::Baseline).unwrap();
+        assert_eq!(baseline.revision, "a");
+
+        assert!(find_reference(&runs, false, Reference::Baseline).is_none());
+    }
+
+    #[test]
+    fn flags_parts_above_threshold() {
+        let before = run("a", true, false, &[(100, 100), (100, 100)]);
+        let after = run("b", true, false, &[(105, 150), (120, 100)]);
+
+        let regressions = find_regressions(&before, &after, Statistic::Mean, 10.0);
+        assert_eq!(regressions.len(), 1);
+        assert_eq!(regressions[0].part, Part::Two);
+        assert_eq!(regressions[0].percent().round(), 20.0);
+
+        let regressions = find_regressions(&before, &after, Statistic::Median, 10.0);
+        assert_eq!(regressions.len(), 1);
+        assert_eq!(regressions[0].part, Part::One);
+    }
+
+    #[test]
+    fn ignores_parts_missing_from_reference() {
+        let before = run("a", true, false, &[(100, 100)]);
+        let after = run("b", true, false, &[(100, 100), (500, 500)]);
+        assert!(find_regressions(&before, &after, Statistic::Mean, 10.0).is_empty());
+    }
+}
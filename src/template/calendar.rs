@@ -0,0 +1,202 @@
+/// Helpers to wait for puzzles to unlock, see [`crate::Day::unlock_time`] for when that happens.
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+/// Source of the current time, which can be replaced in tests.
+pub trait Clock {
+    fn now(&self) -> SystemTime;
+    fn sleep(&mut self, duration: Duration);
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+
+    fn sleep(&mut self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+/// Sleeps until `target` in steps of at most a second, and calls `on_tick` with the remaining time before every step.
+pub fn wait_until(clock: &mut impl Clock, target: SystemTime, mut on_tick: impl FnMut(Duration)) {
+    while let Ok(remaining) = target.duration_since(clock.now()) {
+        if remaining.is_zero() {
+            break;
+        }
+
+        on_tick(remaining);
+
+        // sleeping up to the next full second keeps the countdown in step with the clock.
+        let step = Duration::from_nanos(u64::from(remaining.subsec_nanos()));
+        clock.sleep(if step.is_zero() {
+            Duration::from_secs(1)
+        } else {
+            step
+        });
+    }
+}
+
+/// Formats the remaining time as `HH:MM:SS`, rounded up to the next second.
+#[must_use]
+pub fn format_countdown(remaining: Duration) -> String {
+    let secs = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+    format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+/// Retries an operation with an exponentially increasing delay between the attempts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Backoff {
+    pub attempts: u32,
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            attempts: 6,
+            initial_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+impl Backoff {
+    /// Runs `operation` until it succeeds or all attempts are used up.
+    /// `should_retry` is called with every error and the delay before the next attempt, and decides whether to retry.
+    pub fn retry<T, E>(
+        &self,
+        clock: &mut impl Clock,
+        mut operation: impl FnMut() -> Result<T, E>,
+        mut should_retry: impl FnMut(&E, Duration) -> bool,
+    ) -> Result<T, E> {
+        let mut delay = self.initial_delay;
+        let mut attempt = 1;
+
+        loop {
+            match operation() {
+                Ok(result) => return Ok(result),
+                Err(e) if attempt < self.attempts && should_retry(&e, delay) => {
+                    clock.sleep(delay);
+                    delay = (delay * 2).min(self.max_delay);
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_countdown, wait_until, Backoff, Clock};
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    /// A clock that only advances when sleeping, and records the sleeps.
+    struct FakeClock {
+        now: SystemTime,
+        sleeps: Vec<Duration>,
+    }
+
+    impl FakeClock {
+        fn at(secs: u64) -> Self {
+            Self {
+                now: UNIX_EPOCH + Duration::from_secs(secs),
+                sleeps: vec![],
+            }
+        }
+    }
+
+    impl Clock for FakeClock {
+        fn now(&self) -> SystemTime {
+            self.now
+        }
+
+        fn sleep(&mut self, duration: Duration) {
+            self.now += duration;
+            self.sleeps.push(duration);
+        }
+    }
+
+    #[test]
+    fn counts_down_until_target() {
+        let mut clock = FakeClock::at(100);
+        clock.now += Duration::from_millis(500);
+        let target = UNIX_EPOCH + Duration::from_secs(103);
+
+        let mut ticks = vec![];
+        wait_until(&mut clock, target, |remaining| {
+            ticks.push(format_countdown(remaining));
+        });
+
+        assert_eq!(clock.now, target);
+        assert_eq!(ticks, vec!["00:00:03", "00:00:02", "00:00:01"]);
+    }
+
+    #[test]
+    fn does_not_wait_for_past_target() {
+        let mut clock = FakeClock::at(100);
+        wait_until(&mut clock, UNIX_EPOCH + Duration::from_secs(50), |_| {
+            panic!("unexpected tick")
+        });
+        assert!(clock.sleeps.is_empty());
+    }
+
+    #[test]
+    fn formats_countdown() {
+        assert_eq!(format_countdown(Duration::from_secs(3723)), "01:02:03");
+        assert_eq!(format_countdown(Duration::from_millis(59_001)), "00:01:00");
+    }
+
+    #[test]
+    fn retries_with_backoff() {
+        let mut clock = FakeClock::at(0);
+        let backoff = Backoff {
+            attempts: 5,
+            initial_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(3),
+        };
+
+        let mut calls = 0;
+        let result: Result<(), ()> = backoff.retry(
+            &mut clock,
+            || {
+                calls += 1;
+                Err(())
+            },
+            |_, _| true,
+        );
+
+        assert!(result.is_err());
+        assert_eq!(calls, 5);
+        assert_eq!(clock.sleeps, [1, 2, 3, 3].map(Duration::from_secs).to_vec());
+    }
+
+    #[test]
+    fn stops_retrying_on_success_or_permanent_error() {
+        let mut clock = FakeClock::at(0);
+        let backoff = Backoff::default();
+
+        let mut calls = 0;
+        let result = backoff.retry(
+            &mut clock,
+            || {
+                calls += 1;
+                if calls < 3 {
+                    Err(calls)
+                } else {
+                    Ok(calls)
+                }
+            },
+            |_, _| true,
+        );
+        assert_eq!(result, Ok(3));
+
+        let result: Result<(), &str> = backoff.retry(&mut clock, || Err("fatal"), |_, _| false);
+        assert_eq!(result, Err("fatal"));
+        assert_eq!(clock.sleeps.len(), 2);
+    }
+}
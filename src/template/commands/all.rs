@@ -1,39 +1,68 @@
+use std::fmt::Display;
 use std::io;
+use std::iter::Peekable;
+use std::process;
 
 use crate::template::{
+    bench_history::{self, HistoryOptions, Run},
     readme_benchmarks::{self, Timings},
+    record::{ParseRecord, PartRecord, PartStatus},
+    runner::{print_parse_record, print_record, BenchConfig, Statistic},
     ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
-use crate::{all_days, Day};
-
-pub fn handle(is_release: bool, is_timed: bool) {
-    let mut timings: Vec<Timings> = vec![];
+use crate::{Day, Year};
+
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    year: Year,
+    days: &[Day],
+    is_release: bool,
+    is_timed: bool,
+    bench: &BenchConfig,
+    statistic: Statistic,
+    jobs: Option<usize>,
+    history: &HistoryOptions,
+) {
+    let mut report = Report::new(days.to_vec(), statistic);
+
+    let result = match jobs {
+        Some(0) => {
+            eprintln!("--jobs expects at least one job.");
+            process::exit(1);
+        }
+        Some(jobs) if jobs > 1 => {
+            // concurrently running days would compete for the CPU and skew each other's timings.
+            if is_timed {
+                eprintln!(
+                    "--jobs cannot be combined with --time, benchmarks always run sequentially."
+                );
+                process::exit(1);
+            }
 
-    all_days().for_each(|day| {
-        if day > 1 {
-            println!();
+            child_commands::run_solutions_parallel(year, days, jobs, is_release, &mut report)
         }
+        _ => child_commands::run_solutions(year, days, is_timed, bench, is_release, &mut report),
+    };
 
-        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-        println!("------");
+    if let Err(e) = result {
+        eprintln!("Failed to run solutions: {e}");
+        process::exit(1);
+    }
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    let (timings, records) = report.finish();
 
-        if output.is_empty() {
-            println!("Not solved.");
-        } else {
-            let val = child_commands::parse_exec_time(&output, day);
-            timings.push(val);
-        }
-    });
+    print_failures(&records);
 
     if is_timed {
         let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
 
         println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
 
-        if is_release {
-            match readme_benchmarks::update(timings, total_millis) {
+        update_history(year, &records, is_release, statistic, history);
+
+        // the readme only lists the benchmarks of the default year.
+        if is_release && year.is_default() {
+            match readme_benchmarks::update(timings, total_millis, statistic) {
                 Ok(()) => println!("Successfully updated README with benchmarks."),
                 Err(_) => {
                     eprintln!("Failed to update readme with benchmarks.");
@@ -43,6 +72,160 @@ pub fn handle(is_release: bool, is_timed: bool) {
     }
 }
 
+/// Lists the parts that returned an error, so they do not get lost in the output of the other days.
+fn print_failures(records: &[PartRecord]) {
+    let failures: Vec<&PartRecord> = records
+        .iter()
+        .filter(|record| record.status == PartStatus::Failed)
+        .collect();
+
+    if failures.is_empty() {
+        return;
+    }
+
+    println!("\n{ANSI_BOLD}Failed:{ANSI_RESET}");
+    for record in failures {
+        println!(
+            "  Day {} Part {}: {}",
+            record.day,
+            record.part,
+            record.error.as_deref().unwrap_or_default()
+        );
+    }
+}
+
+/// Compares the run with the reference run from the benchmark history and stores it afterwards.
+fn update_history(
+    year: Year,
+    records: &[PartRecord],
+    is_release: bool,
+    statistic: Statistic,
+    options: &HistoryOptions,
+) {
+    let path = bench_history::get_path(year);
+
+    let runs = match bench_history::load(&path) {
+        Ok(runs) => runs,
+        Err(e) => {
+            eprintln!("Failed to compare benchmarks: {e}");
+            return;
+        }
+    };
+
+    let run = Run::new(records, is_release, options.pin_baseline);
+
+    match bench_history::find_reference(&runs, is_release, options.compare) {
+        Some(reference) => {
+            let regressions =
+                bench_history::find_regressions(reference, &run, statistic, options.threshold);
+
+            if regressions.is_empty() {
+                println!(
+                    "No part got slower by more than {}% compared to {}.",
+                    options.threshold, reference.revision
+                );
+            } else {
+                println!(
+                    "{ANSI_BOLD}Regressions{ANSI_RESET} of more than {}% compared to {}:",
+                    options.threshold, reference.revision
+                );
+                for regression in regressions {
+                    println!("  {regression}");
+                }
+            }
+        }
+        None => println!("No earlier benchmark to compare against."),
+    }
+
+    match bench_history::append(&path, &run) {
+        Ok(()) => println!("Stored benchmark in \"{path}\"."),
+        Err(e) => eprintln!("Failed to store benchmark: {e}"),
+    }
+}
+
+/// Prints the output of the selected days in order while the records of the solutions stream in.
+/// Days without records are reported as not solved.
+struct Report {
+    statistic: Statistic,
+    days: Peekable<std::vec::IntoIter<Day>>,
+    started: bool,
+    current: Option<Day>,
+    parse: Option<ParseRecord>,
+    records: Vec<PartRecord>,
+    timings: Vec<Timings>,
+    finished: Vec<PartRecord>,
+}
+
+impl Report {
+    fn new(days: Vec<Day>, statistic: Statistic) -> Self {
+        Self {
+            statistic,
+            days: days.into_iter().peekable(),
+            started: false,
+            current: None,
+            parse: None,
+            records: vec![],
+            timings: vec![],
+            finished: vec![],
+        }
+    }
+
+    /// Prints the headers of all days up to and including `day`.
+    fn start_day(&mut self, day: Day) {
+        self.finish_day();
+
+        while let Some(next) = self.days.next_if(|next| *next <= day) {
+            if self.started {
+                println!();
+            }
+            self.started = true;
+
+            println!("{ANSI_BOLD}Day {next}{ANSI_RESET}");
+            println!("------");
+
+            if next == day {
+                self.current = Some(day);
+            } else {
+                println!("Not solved.");
+            }
+        }
+    }
+
+    fn add_parse_record(&mut self, record: ParseRecord) {
+        print_parse_record(&record);
+        self.parse = Some(record);
+    }
+
+    fn add_record(&mut self, record: PartRecord) {
+        print_record(&record);
+        self.records.push(record);
+    }
+
+    fn finish_day(&mut self) {
+        let Some(day) = self.current.take() else {
+            return;
+        };
+
+        let parse = self.parse.take();
+
+        if self.records.is_empty() {
+            println!("Not solved.");
+        } else {
+            let val =
+                child_commands::collect_timings(parse.as_ref(), &self.records, day, self.statistic);
+            self.timings.push(val);
+            self.finished.append(&mut self.records);
+        }
+    }
+
+    /// Prints the remaining days and returns the timings and records of all days.
+    fn finish(mut self) -> (Vec<Timings>, Vec<PartRecord>) {
+        self.start_day(crate::day!(25));
+        self.finish_day();
+        (self.timings, self.finished)
+    }
+}
+
 #[derive(Debug)]
 pub enum Error {
     BrokenPipe,
@@ -56,45 +239,72 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::BrokenPipe => write!(f, "the output of the solutions binary could not be read."),
+            Error::Parser(e) => write!(f, "{e}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
 }
 
-/// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// All solutions are compiled into the `solutions` binary, which runs them in-process.
+/// This module encapsulates interaction with this binary, both invoking it as well as collecting the records it emits.
 mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::Day;
+    use super::{Error, Report};
+    use crate::template::record::{DayMarker, ParseRecord, PartRecord, PartStatus};
+    use crate::template::runner::{BenchConfig, Statistic};
+    use crate::{Day, Part, PuzzleId, Year};
     use std::{
+        collections::BTreeMap,
         io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
+        path::{Path, PathBuf},
+        process::{Command, Output, Stdio},
+        sync::{mpsc, Mutex},
         thread,
     };
+    use tinyjson::JsonValue;
+
+    /// Run the solutions of the selected days and feed their output into the report.
+    pub fn run_solutions(
+        year: Year,
+        days: &[Day],
+        is_timed: bool,
+        bench: &BenchConfig,
+        is_release: bool,
+        report: &mut Report,
+    ) -> Result<(), Error> {
+        let mut args: Vec<String> = vec![
+            "run".into(),
+            "--quiet".into(),
+            "--bin".into(),
+            "solutions".into(),
+        ];
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+        if is_release {
+            args.push("--release".into());
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        args.push("--".into());
+        args.push("--format".into());
+        args.push("json".into());
+        args.push("--year".into());
+        args.push(year.to_string());
 
-        if is_release {
-            args.push("--release");
+        for day in days {
+            args.push("--day".into());
+            args.push(day.to_string());
         }
 
         if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+            // mirror `--time` flag and benchmark options to child invocations.
+            args.push("--time".into());
+            args.append(&mut bench.to_args());
         }
 
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward stderr and print records as they arrive, any other output is forwarded verbatim.
 
         let mut cmd = Command::new("cargo")
             .args(&args)
@@ -105,8 +315,6 @@ mod child_commands {
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
-
         let thread = thread::spawn(move || {
             stderr.lines().for_each(|line| {
                 eprintln!("{}", line.unwrap());
@@ -114,76 +322,191 @@ mod child_commands {
         });
 
         for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+            process_line(&line.unwrap(), report);
         }
 
         thread.join().unwrap();
-        cmd.wait()?;
+        let status = cmd.wait()?;
 
-        Ok(output)
-    }
+        // solutions that panic are caught by the binary, so this is a build error or an invalid argument.
+        if !status.success() {
+            return Err(Error::Parser(format!(
+                "`cargo run --bin solutions` exited with {status}."
+            )));
+        }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
-        let mut timings = super::Timings {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
+        Ok(())
+    }
 
-        output
+    /// Run the solutions of the selected days with up to `jobs` days at a time.
+    /// The output of each day is buffered and fed into the report in day order.
+    pub fn run_solutions_parallel(
+        year: Year,
+        days: &[Day],
+        jobs: usize,
+        is_release: bool,
+        report: &mut Report,
+    ) -> Result<(), Error> {
+        let executable = build_solutions(is_release)?;
+
+        let days: Vec<Day> = days
             .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
+            .copied()
+            .filter(|day| Path::new(&PuzzleId::new(year, *day).bin_path()).exists())
+            .collect();
+
+        let queue = Mutex::new(days.iter().copied());
+        let (sender, receiver) = mpsc::channel::<(Day, std::io::Result<Output>)>();
+
+        thread::scope(|scope| {
+            for _ in 0..jobs.min(days.len()) {
+                let sender = sender.clone();
+                let queue = &queue;
+                let executable = &executable;
+
+                scope.spawn(move || loop {
+                    let Some(day) = queue.lock().unwrap().next() else {
+                        break;
+                    };
+
+                    let output = Command::new(executable)
+                        .args(["--format", "json", "--year", &year.to_string()])
+                        .args(["--day", &day.to_string()])
+                        .output();
+
+                    if sender.send((day, output)).is_err() {
+                        break;
+                    }
+                });
+            }
+
+            drop(sender);
+
+            let mut pending = BTreeMap::new();
+
+            for day in &days {
+                while !pending.contains_key(day) {
+                    let Ok((finished, output)) = receiver.recv() else {
+                        break;
+                    };
+                    pending.insert(finished, output);
                 }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
+                let Some(output) = pending.remove(day) else {
+                    return Err(Error::BrokenPipe);
                 };
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+                let output = output?;
+
+                for line in String::from_utf8_lossy(&output.stdout).lines() {
+                    process_line(line, report);
                 }
 
-                timings.total_nanos += nanos;
-            });
+                eprint!("{}", String::from_utf8_lossy(&output.stderr));
+            }
 
-        timings
+            Ok(())
+        })
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+    /// Builds the `solutions` binary and returns the path to its executable.
+    fn build_solutions(is_release: bool) -> Result<PathBuf, Error> {
+        let mut args = vec![
+            "build",
+            "--quiet",
+            "--bin",
+            "solutions",
+            "--message-format=json-render-diagnostics",
+        ];
+
+        if is_release {
+            args.push("--release");
+        }
+
+        let output = Command::new("cargo")
+            .args(&args)
+            .stderr(Stdio::inherit())
+            .output()?;
+
+        if !output.status.success() {
+            return Err(Error::Parser(
+                "failed to build the solutions binary.".into(),
+            ));
+        }
+
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .find_map(|line| {
+                let JsonValue::Object(message) = line.parse::<JsonValue>().ok()? else {
+                    return None;
+                };
+
+                match message.get("executable")? {
+                    JsonValue::String(path) => Some(PathBuf::from(path)),
+                    _ => None,
+                }
+            })
+            .ok_or_else(|| Error::Parser("could not locate the solutions binary.".into()))
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
+    fn process_line(line: &str, report: &mut Report) {
+        if let Some(DayMarker(day)) = DayMarker::from_json(line) {
+            report.start_day(day);
+        } else if let Some(record) = ParseRecord::from_json(line) {
+            report.add_parse_record(record);
+        } else if let Some(record) = PartRecord::from_json(line) {
+            report.add_record(record);
+        } else {
+            println!("{line}");
+        }
+    }
+
+    /// Collects the timings of a day for the readme. The parse step counts towards the total like the parts.
+    pub fn collect_timings(
+        parse: Option<&ParseRecord>,
+        records: &[PartRecord],
+        day: Day,
+        statistic: Statistic,
+    ) -> super::Timings {
+        let mut timings = super::Timings {
+            day,
+            parse: None,
+            part_1: None,
+            part_2: None,
+            total_nanos: 0_f64,
+        };
+
+        if let Some(parse) = parse {
+            let duration = parse.stats.get(statistic);
+            #[allow(clippy::cast_precision_loss)]
+            {
+                timings.total_nanos += duration.as_nanos() as f64;
+            }
+            timings.parse = Some(format!("{duration:.1?}"));
+        }
 
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
+        records
+            .iter()
+            .filter(|record| !matches!(record.status, PartStatus::Unsolved | PartStatus::Failed))
+            .for_each(|record| {
+                let duration = record.stats.get(statistic);
+                let timing_str = if record.status == PartStatus::NotApplicable {
+                    "n/a".into()
+                } else {
+                    #[allow(clippy::cast_precision_loss)]
+                    {
+                        timings.total_nanos += duration.as_nanos() as f64;
+                    }
+                    format!("{duration:.1?}")
+                };
+
+                match record.part {
+                    Part::One => timings.part_1 = Some(timing_str),
+                    Part::Two => timings.part_2 = Some(timing_str),
+                }
+            });
 
-        Some((str_timing, parsed_timing))
+        timings
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -202,53 +525,148 @@ mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
-
-        use crate::day;
+        use super::collect_timings;
+
+        use crate::template::record::{ParseRecord, PartRecord, PartStatus};
+        use crate::template::runner::{BenchStats, Statistic};
+        use crate::{day, Part};
+        use std::time::Duration;
+
+        fn record(part: Part, answer: Option<&str>, samples: &[u64]) -> PartRecord {
+            PartRecord {
+                day: day!(1),
+                part,
+                status: if answer.is_some() {
+                    PartStatus::Solved
+                } else {
+                    PartStatus::Unsolved
+                },
+                answer: answer.map(String::from),
+                error: None,
+                stats: BenchStats::from_samples(
+                    &samples
+                        .iter()
+                        .copied()
+                        .map(Duration::from_nanos)
+                        .collect::<Vec<_>>(),
+                ),
+            }
+        }
 
         #[test]
         fn test_well_formed() {
-            let res = parse_exec_time(
+            let res = collect_timings(
+                None,
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
+                    record(Part::One, Some("0"), &[74, 74, 74]),
+                    record(Part::Two, Some("10"), &[74_130_000]),
                 ],
                 day!(1),
+                Statistic::Mean,
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_approx_eq!(res.total_nanos, 74130074_f64);
+            assert_eq!(res.part_1.unwrap(), "74.0ns");
+            assert_eq!(res.part_2.unwrap(), "74.1ms");
         }
 
         #[test]
         fn test_patterns_in_input() {
-            let res = parse_exec_time(
+            let res = collect_timings(
+                None,
                 &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
+                    record(Part::One, Some("@ @ @ ( ) ms"), &[2_000_000_000]),
+                    record(Part::Two, Some("10s (100ms @ 1 samples)"), &[100_000_000]),
                 ],
                 day!(1),
+                Statistic::Mean,
             );
             assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.part_1.unwrap(), "2.0s");
+            assert_eq!(res.part_2.unwrap(), "100.0ms");
         }
 
         #[test]
         fn test_missing_parts() {
-            let res = parse_exec_time(
+            let res = collect_timings(
+                None,
                 &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
+                    record(Part::One, None, &[10]),
+                    record(Part::Two, None, &[10]),
                 ],
                 day!(1),
+                Statistic::Mean,
             );
             assert_approx_eq!(res.total_nanos, 0_f64);
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn test_not_applicable_part() {
+            let mut not_applicable = record(Part::Two, None, &[0]);
+            not_applicable.status = PartStatus::NotApplicable;
+
+            let res = collect_timings(
+                None,
+                &[record(Part::One, Some("0"), &[74]), not_applicable],
+                day!(25),
+                Statistic::Mean,
+            );
+            assert_approx_eq!(res.total_nanos, 74_f64);
+            assert_eq!(res.part_2.unwrap(), "n/a");
+        }
+
+        #[test]
+        fn test_failed_part() {
+            let mut failed = record(Part::Two, None, &[50]);
+            failed.status = PartStatus::Failed;
+            failed.error = Some("unexpected tile".into());
+
+            let res = collect_timings(
+                None,
+                &[record(Part::One, Some("0"), &[74]), failed],
+                day!(10),
+                Statistic::Mean,
+            );
+            assert_approx_eq!(res.total_nanos, 74_f64);
+            assert_eq!(res.part_2.is_none(), true);
+        }
+
+        #[test]
+        fn test_parse_step() {
+            let parse = ParseRecord {
+                day: day!(14),
+                stats: BenchStats::from_samples(&[Duration::from_nanos(1_000)]),
+            };
+
+            let res = collect_timings(
+                Some(&parse),
+                &[
+                    record(Part::One, Some("0"), &[74]),
+                    record(Part::Two, Some("10"), &[26]),
+                ],
+                day!(14),
+                Statistic::Mean,
+            );
+            assert_approx_eq!(res.total_nanos, 1100_f64);
+            assert_eq!(res.parse.unwrap(), "1.0µs");
+            assert_eq!(res.part_1.unwrap(), "74.0ns");
+        }
+
+        #[test]
+        fn test_median() {
+            let res = collect_timings(
+                None,
+                &[
+                    record(Part::One, Some("0"), &[70, 72, 73, 1000]),
+                    record(Part::Two, Some("10"), &[70_000_000, 71_000_000, 90_000_000]),
+                ],
+                day!(1),
+                Statistic::Median,
+            );
+            assert_approx_eq!(res.total_nanos, 71000072_f64);
+            assert_eq!(res.part_1.unwrap(), "72.0ns");
+            assert_eq!(res.part_2.unwrap(), "71.0ms");
+        }
     }
 }
@@ -1,15 +1,67 @@
 use crate::template::aoc_cli;
-use crate::Day;
+use crate::template::calendar::{self, Backoff, SystemClock};
+use crate::PuzzleId;
+use std::io::{stdout, Write};
 use std::process;
+use std::time::SystemTime;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+pub fn handle(puzzle: PuzzleId, refresh: bool, wait: bool) {
+    let PuzzleId { year, day } = puzzle;
+
+    if wait {
+        wait_for_unlock(puzzle);
+    } else if let Ok(remaining) = day.unlock_time(year).duration_since(SystemTime::now()) {
+        eprintln!(
+            "Day {day} of {year} unlocks in {}, pass --wait to download it once it unlocks.",
+            calendar::format_countdown(remaining)
+        );
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    let mut clock = SystemClock;
+    let backoff = if wait {
+        Backoff::default()
+    } else {
+        Backoff {
+            attempts: 1,
+            ..Backoff::default()
+        }
+    };
+
+    let result = backoff.retry(
+        &mut clock,
+        || aoc_cli::download(puzzle, refresh),
+        |e, delay| {
+            if !e.is_transient() {
+                return false;
+            }
+            eprintln!("failed to access Advent of Code: {e}");
+            eprintln!("Retrying in {}s...", delay.as_secs());
+            true
+        },
+    );
+
+    if let Err(e) = result {
+        eprintln!("failed to access Advent of Code: {e}");
         process::exit(1);
     };
 }
+
+/// Shows a countdown until the puzzle unlocks.
+fn wait_for_unlock(puzzle: PuzzleId) {
+    let PuzzleId { year, day } = puzzle;
+    let mut waited = false;
+
+    calendar::wait_until(&mut SystemClock, day.unlock_time(year), |remaining| {
+        waited = true;
+        print!(
+            "\r⏳ Day {day} unlocks in {}",
+            calendar::format_countdown(remaining)
+        );
+        let _ = stdout().flush();
+    });
+
+    if waited {
+        println!("\r🔓 Day {day} has unlocked.          ");
+    }
+}
@@ -1,5 +1,8 @@
 pub mod all;
 pub mod download;
 pub mod read;
+pub mod rollover;
 pub mod scaffold;
 pub mod solve;
+pub mod today;
+pub mod verify;
@@ -1,16 +1,11 @@
 use std::process;
 
 use crate::template::aoc_cli;
-use crate::Day;
+use crate::PuzzleId;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
-
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+pub fn handle(puzzle: PuzzleId, refresh: bool) {
+    if let Err(e) = aoc_cli::read(puzzle, refresh) {
+        eprintln!("failed to access Advent of Code: {e}");
         process::exit(1);
     };
 }
@@ -0,0 +1,263 @@
+/// Archives the default year into the namespaced layout and makes another year the default year.
+/// Solutions move from `src/bin/NN.rs` to `src/bin/<year>-NN.rs`, data moves from `data/` to `data/<year>/`.
+use std::fmt::Display;
+use std::path::Path;
+use std::process::{self, Command};
+use std::{fs, io};
+
+use crate::template::{default_year, readme_benchmarks};
+use crate::{Day, Year};
+
+const CONFIG_PATH: &str = ".cargo/config.toml";
+
+/// The folders of the single-year layout that hold one file per day.
+const DATA_FOLDERS: [&str; 5] = ["inputs", "examples", "puzzles", "answers", "submissions"];
+
+/// The folders the scaffolding expects to exist.
+const SCAFFOLD_FOLDERS: [&str; 3] = ["inputs", "examples", "puzzles"];
+
+#[derive(Debug)]
+pub enum Error {
+    SameYear(Year),
+    DirtyTree(String),
+    Conflict(String),
+    Config(String),
+    Readme(readme_benchmarks::Error),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl From<readme_benchmarks::Error> for Error {
+    fn from(e: readme_benchmarks::Error) -> Self {
+        Error::Readme(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::SameYear(year) => write!(f, "{year} is already the current year"),
+            Error::DirtyTree(status) => write!(
+                f,
+                "the working tree has uncommitted changes or untracked files, commit or stash them first:\n{status}"
+            ),
+            Error::Conflict(path) => write!(f, "\"{path}\" already exists"),
+            Error::Config(e) => write!(f, "could not update \"{CONFIG_PATH}\": {e}"),
+            Error::Readme(e) => write!(f, "could not archive the readme benchmarks: {e:?}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+pub fn handle(year: Year) {
+    let archived = default_year();
+
+    if let Err(e) = rollover(archived, year) {
+        eprintln!("Failed to roll over: {e}");
+        process::exit(1);
+    }
+
+    println!("📦 Archived {archived} to \"data/{archived}\" and \"src/bin/{archived}-*.rs\".");
+    println!("🎄 {year} is the current year, type `cargo scaffold 1` to get started.");
+    println!("🎄 Solutions of {archived} keep running with `--year {archived}`.");
+}
+
+fn rollover(archived: Year, year: Year) -> Result<(), Error> {
+    if archived == year {
+        return Err(Error::SameYear(year));
+    }
+
+    ensure_clean_tree()?;
+
+    let config = fs::read_to_string(CONFIG_PATH)?;
+    let config = set_config_year(&config, year)
+        .ok_or_else(|| Error::Config("could not find \"AOC_YEAR\"".into()))?;
+
+    // everything is checked up front, so the tree is not left half-archived.
+    let data_moves: Vec<(String, String)> = DATA_FOLDERS
+        .iter()
+        .map(|folder| {
+            (
+                format!("data/{folder}"),
+                format!("data/{archived}/{folder}"),
+            )
+        })
+        .chain([(
+            "data/benchmarks.jsonl".into(),
+            format!("data/{archived}/benchmarks.jsonl"),
+        )])
+        .filter(|(from, _)| Path::new(from).exists())
+        .collect();
+    let module_moves: Vec<(String, String)> = solution_modules()?
+        .iter()
+        .map(|name| {
+            (
+                format!("src/bin/{name}.rs"),
+                format!("src/bin/{archived}-{name}.rs"),
+            )
+        })
+        .collect();
+
+    let conflict = data_moves
+        .iter()
+        .chain(&module_moves)
+        .map(|(_, to)| to.clone())
+        .chain([format!("data/{archived}/benchmarks.md")])
+        .find(|to| Path::new(to).exists());
+    if let Some(to) = conflict {
+        return Err(Error::Conflict(to));
+    }
+
+    readme_benchmarks::archive(archived)?;
+
+    for (from, to) in &data_moves {
+        fs::rename(from, to)?;
+    }
+
+    for (from, to) in &module_moves {
+        let source = fs::read_to_string(from)?;
+        fs::write(to, archive_module(&source, archived))?;
+        fs::remove_file(from)?;
+    }
+
+    for folder in SCAFFOLD_FOLDERS {
+        fs::create_dir_all(format!("data/{folder}"))?;
+        fs::write(format!("data/{folder}/.keep"), "")?;
+    }
+
+    fs::write(CONFIG_PATH, config)?;
+    Ok(())
+}
+
+/// Refuses to touch a tree with uncommitted changes, so the rollover can be reviewed and reverted with git.
+/// Untracked files count as well, as git could not restore a solution that was never committed.
+fn ensure_clean_tree() -> Result<(), Error> {
+    let output = Command::new("git")
+        .args(["status", "--porcelain"])
+        .output()?;
+
+    if !output.status.success() {
+        return Err(Error::DirtyTree(
+            String::from_utf8_lossy(&output.stderr).into_owned(),
+        ));
+    }
+
+    let status = String::from_utf8_lossy(&output.stdout);
+    if status.trim().is_empty() {
+        Ok(())
+    } else {
+        Err(Error::DirtyTree(status.into_owned()))
+    }
+}
+
+/// The names of the solutions of the default year, e.g. `05` for `src/bin/05.rs`.
+fn solution_modules() -> Result<Vec<String>, Error> {
+    let mut names: Vec<String> = fs::read_dir("src/bin")?
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            let name = name.strip_suffix(".rs")?;
+            name.parse::<Day>().ok()?;
+            Some(name.to_string())
+        })
+        .collect();
+    names.sort();
+    Ok(names)
+}
+
+/// Rewrites a solution of the default year to name its year and read its files from the namespaced layout.
+fn archive_module(source: &str, year: Year) -> String {
+    let source = source
+        .replace("read_file(", "read_puzzle_file(")
+        .replace("read_file_part(", "read_puzzle_file_part(")
+        .replace("\"examples\", DAY", "\"examples\", PUZZLE")
+        .replace("\"inputs\", DAY", "\"inputs\", PUZZLE");
+
+    let Some(start) = source.find("solution!(") else {
+        return source;
+    };
+    // the year follows the day, which is followed by `)`, by options like `, single_part)` or by `=> Solution)`.
+    let Some(end) = source[start..].find([',', ')', '=']).map(|end| start + end) else {
+        return source;
+    };
+    let end = source[..end].trim_end().len();
+
+    format!("{}, {year}{}", &source[..end], &source[end..])
+}
+
+/// Replaces the value of `AOC_YEAR` in the cargo config.
+fn set_config_year(config: &str, year: Year) -> Option<String> {
+    let mut found = false;
+
+    let lines: Vec<String> = config
+        .lines()
+        .map(|line| {
+            if line.trim_start().starts_with("AOC_YEAR") {
+                found = true;
+                format!("AOC_YEAR = \"{year}\"")
+            } else {
+                line.to_string()
+            }
+        })
+        .collect();
+
+    found.then(|| format!("{}\n", lines.join("\n")))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{archive_module, set_config_year};
+    use crate::year;
+
+    #[test]
+    fn archives_module() {
+        let source = [
+            "advent_of_code::solution!(8);",
+            "",
+            "fn test_part_one() {",
+            "    let result = part_one(&advent_of_code::template::read_file(\"examples\", DAY));",
+            "    let result = part_two(&advent_of_code::template::read_file_part(",
+            "        \"examples\", DAY, 2,",
+            "    ));",
+            "}",
+        ]
+        .join("\n");
+
+        let expected = [
+            "advent_of_code::solution!(8, 2023);",
+            "",
+            "fn test_part_one() {",
+            "    let result = part_one(&advent_of_code::template::read_puzzle_file(\"examples\", PUZZLE));",
+            "    let result = part_two(&advent_of_code::template::read_puzzle_file_part(",
+            "        \"examples\", PUZZLE, 2,",
+            "    ));",
+            "}",
+        ]
+        .join("\n");
+
+        assert_eq!(archive_module(&source, year!(2023)), expected);
+        assert_eq!(
+            archive_module("advent_of_code::solution!(25, single_part);", year!(2023)),
+            "advent_of_code::solution!(25, 2023, single_part);"
+        );
+        assert_eq!(
+            archive_module("advent_of_code::solution!(14 => Dish);", year!(2023)),
+            "advent_of_code::solution!(14, 2023 => Dish);"
+        );
+    }
+
+    #[test]
+    fn updates_config_year() {
+        let config = "[alias]\nsolve = \"run -- solve\"\n\n[env]\nAOC_YEAR = \"2023\"\n";
+        assert_eq!(
+            set_config_year(config, year!(2024)).unwrap(),
+            "[alias]\nsolve = \"run -- solve\"\n\n[env]\nAOC_YEAR = \"2024\"\n"
+        );
+        assert!(set_config_year("[alias]\n", year!(2024)).is_none());
+    }
+}
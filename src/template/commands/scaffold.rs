@@ -1,10 +1,11 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::Day;
+use crate::PuzzleId;
 
 const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(DAY_NUMBER);
 
@@ -16,21 +17,21 @@ pub fn part_two(input: &str) -> Option<u32> {
     None
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+advent_of_code::examples! {
+    part_one: [],
+    part_two: [],
+}
+"#;
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
-    }
+/// Day 25 only has one part, so its template has no `part_two`.
+const SINGLE_PART_MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(DAY_NUMBER, single_part);
 
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
-    }
+pub fn part_one(input: &str) -> Option<u32> {
+    None
+}
+
+advent_of_code::examples! {
+    part_one: [],
 }
 "#;
 
@@ -39,13 +40,42 @@ fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Fills in the template for a puzzle. Solutions of years other than the default year name their year.
+fn module_contents(puzzle: PuzzleId) -> String {
+    let day = puzzle.day.into_inner().to_string();
+    let template = if puzzle.day == 25 {
+        SINGLE_PART_MODULE_TEMPLATE
+    } else {
+        MODULE_TEMPLATE
+    };
+
+    if puzzle.year.is_default() {
+        template.replace("DAY_NUMBER", &day)
+    } else {
+        template.replace("DAY_NUMBER", &format!("{day}, {}", puzzle.year))
+    }
+}
+
+pub fn handle(puzzle: PuzzleId) {
+    let input_path = puzzle.data_file("inputs", "txt");
+    let example_path = puzzle.data_file("examples", "txt");
+    let module_path = puzzle.bin_path();
+
+    for path in [&input_path, &example_path] {
+        if let Some(dir) = Path::new(path).parent() {
+            if let Err(e) = fs::create_dir_all(dir) {
+                eprintln!("Failed to create directory \"{}\": {e}", dir.display());
+                process::exit(1);
+            }
+        }
+    }
 
     let mut file = match safe_create_file(&module_path) {
         Ok(file) => file,
@@ -55,11 +85,7 @@ pub fn handle(day: Day) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("DAY_NUMBER", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    match file.write_all(module_contents(puzzle).as_bytes()) {
         Ok(()) => {
             println!("Created module file \"{}\"", &module_path);
         }
@@ -90,5 +116,12 @@ pub fn handle(day: Day) {
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {}` to run your solution.", day);
+    if puzzle.year.is_default() {
+        println!("🎄 Type `cargo solve {}` to run your solution.", puzzle.day);
+    } else {
+        println!(
+            "🎄 Type `cargo solve {} --year {}` to run your solution.",
+            puzzle.day, puzzle.year
+        );
+    }
 }
@@ -1,9 +1,22 @@
+use std::fs;
 use std::process::{Command, Stdio};
 
-use crate::Day;
+use crate::template::answers;
+use crate::template::record::{OutputFormat, PartRecord, PartStatus};
+use crate::template::runner::BenchConfig;
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+use crate::{Part, PuzzleId};
 
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+pub fn handle(
+    puzzle: PuzzleId,
+    release: bool,
+    time: bool,
+    bench: &BenchConfig,
+    format: OutputFormat,
+    submit_part: Option<Part>,
+    input: Option<&str>,
+) {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), puzzle.bin_name()];
 
     if release {
         cmd_args.push("--release".to_string());
@@ -16,8 +29,19 @@ pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
+    if let Some(input) = input {
+        cmd_args.push("--input".to_string());
+        cmd_args.push(input.to_string());
+    }
+
+    if format == OutputFormat::Json {
+        cmd_args.push("--format".to_string());
+        cmd_args.push("json".to_string());
+    }
+
     if time {
         cmd_args.push("--time".to_string());
+        cmd_args.append(&mut bench.to_args());
     }
 
     let mut cmd = Command::new("cargo")
@@ -29,3 +53,284 @@ pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
 
     cmd.wait().unwrap();
 }
+
+/// An example file of a day, e.g. `08-2.txt`, with the expected answers recorded next to it in `08-2.toml`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Example {
+    name: String,
+    input_path: String,
+    answers_path: String,
+}
+
+/// Runs both parts of a solution against every example of the day and prints a table of the answers.
+/// Returns `false` if the day has no examples, a part failed or an answer differs from its expected answer.
+#[must_use]
+pub fn handle_examples(puzzle: PuzzleId, release: bool) -> bool {
+    let examples = find_examples(puzzle);
+
+    if examples.is_empty() {
+        eprintln!(
+            "No examples of {puzzle} found, save them as \"{}\" or \"{}/examples/{}-<n>.txt\".",
+            puzzle.data_file("examples", "txt"),
+            puzzle.year.data_dir(),
+            puzzle.day
+        );
+        return false;
+    }
+
+    let mut rows = vec![];
+    let mut mismatches = 0;
+
+    for example in &examples {
+        let expected = match answers::load_path(&example.answers_path) {
+            Ok(expected) => expected,
+            Err(e) => {
+                eprintln!("Failed to load expected answers of {}: {e}", example.name);
+                return false;
+            }
+        };
+
+        let records = run_example(puzzle, release, example);
+
+        let cells: Vec<(String, bool)> = [Part::One, Part::Two]
+            .into_iter()
+            .map(|part| {
+                let record = records
+                    .as_ref()
+                    .ok()
+                    .and_then(|records| records.iter().find(|record| record.part == part));
+                format_cell(record, expected.get(part), records.is_err())
+            })
+            .collect();
+
+        mismatches += cells.iter().filter(|(_, mismatch)| *mismatch).count();
+        rows.push([example.name.clone(), cells[0].0.clone(), cells[1].0.clone()]);
+    }
+
+    print_table(["Example", "Part 1", "Part 2"], &rows);
+    mismatches == 0
+}
+
+/// Finds the examples of a day, i.e. `NN.txt` followed by `NN-1.txt`, `NN-2.txt`, ... in ascending order.
+fn find_examples(puzzle: PuzzleId) -> Vec<Example> {
+    let dir = format!("{}/examples", puzzle.year.data_dir());
+
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return vec![];
+    };
+
+    let names: Vec<String> = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .collect();
+
+    example_names(puzzle, &names)
+        .into_iter()
+        .map(|stem| Example {
+            name: format!("{stem}.txt"),
+            input_path: format!("{dir}/{stem}.txt"),
+            answers_path: format!("{dir}/{stem}.toml"),
+        })
+        .collect()
+}
+
+/// Filters the file names of the examples of a day and returns them without extension, in ascending order.
+fn example_names(puzzle: PuzzleId, names: &[String]) -> Vec<String> {
+    let day = puzzle.day.to_string();
+
+    let mut examples: Vec<(u32, String)> = names
+        .iter()
+        .filter_map(|name| {
+            let stem = name.strip_suffix(".txt")?;
+            let rest = stem.strip_prefix(&day)?;
+
+            if rest.is_empty() {
+                return Some((0, stem.to_string()));
+            }
+
+            let index = rest.strip_prefix('-')?.parse().ok()?;
+            Some((index, stem.to_string()))
+        })
+        .collect();
+
+    examples.sort();
+    examples.into_iter().map(|(_, stem)| stem).collect()
+}
+
+/// Runs a solution against an example and collects the records of its parts.
+/// Fails with the last line of the error output if the solution did not run successfully, e.g. when a part panicked.
+fn run_example(
+    puzzle: PuzzleId,
+    release: bool,
+    example: &Example,
+) -> Result<Vec<PartRecord>, String> {
+    let mut args = vec!["run", "--quiet", "--bin"];
+    let bin_name = puzzle.bin_name();
+    args.push(&bin_name);
+
+    if release {
+        args.push("--release");
+    }
+
+    args.extend(["--", "--format", "json", "--input", &example.input_path]);
+
+    // without a backtrace, the message of a panic is the last line besides notes.
+    let output = Command::new("cargo")
+        .args(&args)
+        .env("RUST_BACKTRACE", "0")
+        .output()
+        .map_err(|e| e.to_string())?;
+
+    let records: Vec<PartRecord> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(PartRecord::from_json)
+        .collect();
+
+    for record in &records {
+        if let Some(error) = &record.error {
+            eprintln!("{} part {}: {error}", example.name, record.part);
+        }
+    }
+
+    if output.status.success() {
+        Ok(records)
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr
+            .lines()
+            .rev()
+            .find(|line| !line.trim().is_empty() && !line.starts_with("note:"));
+        eprintln!("{}: {}", example.name, reason.unwrap_or("failed to run"));
+        Err(reason.unwrap_or_default().to_string())
+    }
+}
+
+/// Formats the answer of a part next to its expected answer, and whether the two differ.
+fn format_cell(
+    record: Option<&PartRecord>,
+    expected: Option<&str>,
+    failed: bool,
+) -> (String, bool) {
+    if record.is_some_and(|record| record.status == PartStatus::NotApplicable) {
+        return ("n/a".into(), false);
+    }
+
+    let actual = record
+        .filter(|record| record.status == PartStatus::Solved)
+        .and_then(|record| record.answer.as_deref());
+
+    let is_failed = record.map_or(failed, |record| record.status == PartStatus::Failed);
+    let missing = if is_failed { "failed" } else { "-" };
+
+    match (actual, expected) {
+        (Some(actual), Some(expected)) if actual == expected => (format!("{actual} ✔"), false),
+        (actual, Some(expected)) => (
+            format!("{} ✖ expected {expected}", actual.unwrap_or(missing)),
+            true,
+        ),
+        (actual, None) => (actual.unwrap_or(missing).into(), is_failed),
+    }
+}
+
+fn print_table(header: [&str; 3], rows: &[[String; 3]]) {
+    let widths: Vec<usize> = (0..3)
+        .map(|column| {
+            rows.iter()
+                .map(|row| row[column].chars().count())
+                .chain([header[column].len()])
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let format_row = |row: [&str; 3]| {
+        row.iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join(" | ")
+            .trim_end()
+            .to_string()
+    };
+
+    println!("{ANSI_BOLD}{}{ANSI_RESET}", format_row(header));
+    for row in rows {
+        println!("{}", format_row([&row[0], &row[1], &row[2]]));
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{example_names, format_cell};
+    use crate::template::record::{PartRecord, PartStatus};
+    use crate::template::runner::BenchStats;
+    use crate::{day, year, Part, PuzzleId};
+    use std::time::Duration;
+
+    fn record(status: PartStatus, answer: Option<&str>) -> PartRecord {
+        PartRecord {
+            day: day!(8),
+            part: Part::One,
+            status,
+            answer: answer.map(String::from),
+            error: None,
+            stats: BenchStats::from_samples(&[Duration::ZERO]),
+        }
+    }
+
+    #[test]
+    fn finds_example_names() {
+        let names = [
+            "08-10.txt",
+            "08-2.txt",
+            "08.txt",
+            "08-2.toml",
+            "08-1.txt",
+            "18.txt",
+            "08-x.txt",
+        ]
+        .map(String::from);
+
+        assert_eq!(
+            example_names(PuzzleId::new(year!(2023), day!(8)), &names),
+            ["08", "08-1", "08-2", "08-10"]
+        );
+    }
+
+    #[test]
+    fn formats_cells() {
+        let solved = record(PartStatus::Solved, Some("42"));
+        let unsolved = record(PartStatus::Unsolved, None);
+        let not_applicable = record(PartStatus::NotApplicable, None);
+
+        assert_eq!(
+            format_cell(Some(&solved), None, false),
+            ("42".into(), false)
+        );
+        assert_eq!(
+            format_cell(Some(&solved), Some("42"), false),
+            ("42 ✔".into(), false)
+        );
+        assert_eq!(
+            format_cell(Some(&solved), Some("6"), false),
+            ("42 ✖ expected 6".into(), true)
+        );
+        assert_eq!(
+            format_cell(Some(&unsolved), Some("6"), false),
+            ("- ✖ expected 6".into(), true)
+        );
+        assert_eq!(format_cell(None, None, true), ("failed".into(), true));
+        assert_eq!(
+            format_cell(Some(&record(PartStatus::Failed, None)), None, false),
+            ("failed".into(), true)
+        );
+        assert_eq!(
+            format_cell(Some(&record(PartStatus::Failed, None)), Some("6"), false),
+            ("failed ✖ expected 6".into(), true)
+        );
+        assert_eq!(
+            format_cell(Some(&not_applicable), None, false),
+            ("n/a".into(), false)
+        );
+    }
+}
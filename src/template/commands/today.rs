@@ -0,0 +1,21 @@
+use std::path::Path;
+use std::process;
+use std::time::SystemTime;
+
+use crate::template::commands::{download, scaffold};
+use crate::Day;
+
+/// Scaffolds the puzzle that unlocked today unless it already exists, and downloads its input and description.
+pub fn handle() {
+    let Some(puzzle) = Day::today(SystemTime::now()) else {
+        eprintln!("There is no puzzle today, puzzles unlock from December 1st to 25th.");
+        process::exit(1);
+    };
+
+    if !Path::new(&puzzle.bin_path()).exists() {
+        scaffold::handle(puzzle);
+    }
+
+    // the website may lag behind right after the puzzle unlocked, waiting retries the download.
+    download::handle(puzzle, false, true);
+}
@@ -0,0 +1,279 @@
+use std::io;
+use std::path::Path;
+use std::process::{self, Command, Stdio};
+
+use crate::template::answers::{self, Answers};
+use crate::template::record::{PartRecord, PartStatus};
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+use crate::{all_days, Part, PuzzleId, Year};
+
+/// The outcome of comparing a part's answer with its recorded answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Verdict {
+    Pass,
+    Fail {
+        expected: String,
+        actual: Option<String>,
+    },
+    /// The part returned an answer, but no answer has been recorded yet.
+    Unrecorded(String),
+    /// The part is neither solved nor recorded.
+    Unsolved,
+    /// The day has no such part, e.g. the second part of day 25.
+    NotApplicable,
+}
+
+impl Verdict {
+    fn new(expected: Option<&str>, actual: Option<&str>) -> Self {
+        match (expected, actual) {
+            (Some(expected), Some(actual)) if expected == actual => Verdict::Pass,
+            (Some(expected), actual) => Verdict::Fail {
+                expected: expected.into(),
+                actual: actual.map(String::from),
+            },
+            (None, Some(actual)) => Verdict::Unrecorded(actual.into()),
+            (None, None) => Verdict::Unsolved,
+        }
+    }
+
+    fn symbol(&self) -> &'static str {
+        match self {
+            Verdict::Pass => "✔",
+            Verdict::Fail { .. } => "✖",
+            Verdict::Unrecorded(_) => "?",
+            Verdict::Unsolved => "-",
+            Verdict::NotApplicable => "n/a",
+        }
+    }
+}
+
+pub fn handle(year: Year, release: bool, record: bool) {
+    let records = match run_solutions(year, release) {
+        Ok(records) => records,
+        Err(e) => {
+            eprintln!("Failed to run solutions: {e}");
+            process::exit(1);
+        }
+    };
+
+    let mut failures = vec![];
+
+    println!("{ANSI_BOLD}Day | Part 1 | Part 2{ANSI_RESET}");
+
+    for day in all_days() {
+        let puzzle = PuzzleId::new(year, day);
+
+        // a scaffolded day without records failed to run, which is checked against its answers as well.
+        if !Path::new(&puzzle.bin_path()).exists() {
+            continue;
+        }
+
+        let day_records: Vec<&PartRecord> = records.iter().filter(|r| r.day == day).collect();
+
+        let mut answers = match answers::load(puzzle) {
+            Ok(answers) => answers,
+            Err(e) => {
+                eprintln!("Failed to load answers of day {day}: {e}");
+                process::exit(1);
+            }
+        };
+
+        let verdicts = verdicts(&answers, &day_records);
+
+        println!(
+            " {day} |   {}    |   {}",
+            verdicts[0].symbol(),
+            verdicts[1].symbol()
+        );
+
+        for (part, verdict) in [Part::One, Part::Two].iter().zip(&verdicts) {
+            if let Verdict::Fail { expected, actual } = verdict {
+                failures.push(format!(
+                    "Day {day} Part {part}: expected \"{expected}\", got {}",
+                    actual
+                        .as_ref()
+                        .map_or("no answer".into(), |a| format!("\"{a}\""))
+                ));
+            }
+        }
+
+        if record {
+            record_answers(puzzle, &mut answers, &verdicts);
+        }
+    }
+
+    println!("---");
+    println!("✔ = correct, ✖ = wrong, ? = no recorded answer, - = not solved, n/a = no such part");
+
+    if !failures.is_empty() {
+        println!();
+        for failure in &failures {
+            println!("{failure}");
+        }
+        process::exit(1);
+    }
+}
+
+/// Compares the records of a day with its recorded answers.
+/// A part without a record counts as unsolved, e.g. when the day failed to run.
+fn verdicts(answers: &Answers, day_records: &[&PartRecord]) -> Vec<Verdict> {
+    [Part::One, Part::Two]
+        .into_iter()
+        .map(|part| {
+            let record = day_records.iter().find(|r| r.part == part);
+
+            if record.is_some_and(|r| r.status == PartStatus::NotApplicable) {
+                return Verdict::NotApplicable;
+            }
+
+            let actual = record
+                .filter(|r| r.status == PartStatus::Solved)
+                .and_then(|r| r.answer.as_deref());
+            Verdict::new(answers.get(part), actual)
+        })
+        .collect()
+}
+
+/// Records the answers of parts that do not have a recorded answer yet.
+/// Answers that differ from a recorded one are never overwritten.
+fn record_answers(puzzle: PuzzleId, answers: &mut Answers, verdicts: &[Verdict]) {
+    let mut changed = false;
+
+    for (part, verdict) in [Part::One, Part::Two].into_iter().zip(verdicts) {
+        if let Verdict::Unrecorded(answer) = verdict {
+            answers.set(part, answer.clone());
+            changed = true;
+        }
+    }
+
+    if changed {
+        match answers::store(puzzle, answers) {
+            Ok(()) => println!("    recorded answers to \"{}\"", answers::get_path(puzzle)),
+            Err(e) => eprintln!("Failed to record answers of {puzzle}: {e}"),
+        }
+    }
+}
+
+/// Runs all scaffolded days in-process and collects their records.
+fn run_solutions(year: Year, release: bool) -> io::Result<Vec<PartRecord>> {
+    let mut args = vec!["run", "--quiet", "--bin", "solutions"];
+
+    if release {
+        args.push("--release");
+    }
+
+    let year = year.to_string();
+    args.extend(["--", "--format", "json", "--year", &year]);
+
+    let output = Command::new("cargo")
+        .args(&args)
+        .stderr(Stdio::inherit())
+        .output()?;
+
+    // without this check, a build error would look like a year without any solved parts.
+    if !output.status.success() {
+        return Err(io::Error::other(format!(
+            "`cargo run --bin solutions` exited with {}",
+            output.status
+        )));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(PartRecord::from_json)
+        .collect())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{verdicts, Verdict};
+    use crate::template::answers::Answers;
+    use crate::template::record::{PartRecord, PartStatus};
+    use crate::template::runner::BenchStats;
+    use crate::{day, Part};
+    use std::time::Duration;
+
+    fn solved(part: Part, answer: &str) -> PartRecord {
+        PartRecord {
+            day: day!(1),
+            part,
+            status: PartStatus::Solved,
+            answer: Some(answer.into()),
+            error: None,
+            stats: BenchStats::from_samples(&[Duration::ZERO]),
+        }
+    }
+
+    #[test]
+    fn compares_answers() {
+        assert_eq!(Verdict::new(Some("42"), Some("42")), Verdict::Pass);
+        assert_eq!(
+            Verdict::new(Some("42"), Some("43")),
+            Verdict::Fail {
+                expected: "42".into(),
+                actual: Some("43".into())
+            }
+        );
+        assert_eq!(
+            Verdict::new(Some("42"), None),
+            Verdict::Fail {
+                expected: "42".into(),
+                actual: None
+            }
+        );
+        assert_eq!(
+            Verdict::new(None, Some("42")),
+            Verdict::Unrecorded("42".into())
+        );
+        assert_eq!(Verdict::new(None, None), Verdict::Unsolved);
+    }
+
+    #[test]
+    fn compares_records_of_a_day() {
+        let answers = Answers {
+            part_one: Some("42".into()),
+            part_two: None,
+        };
+        let part_one = solved(Part::One, "42");
+        let part_two = solved(Part::Two, "7");
+
+        assert_eq!(
+            verdicts(&answers, &[&part_one, &part_two]),
+            vec![Verdict::Pass, Verdict::Unrecorded("7".into())]
+        );
+    }
+
+    #[test]
+    fn skips_parts_that_do_not_exist() {
+        let part_one = solved(Part::One, "42");
+        let part_two = PartRecord {
+            status: PartStatus::NotApplicable,
+            answer: None,
+            ..solved(Part::Two, "")
+        };
+
+        assert_eq!(
+            verdicts(&Answers::default(), &[&part_one, &part_two]),
+            vec![Verdict::Unrecorded("42".into()), Verdict::NotApplicable]
+        );
+    }
+
+    #[test]
+    fn fails_recorded_answers_of_a_day_without_records() {
+        let answers = Answers {
+            part_one: Some("42".into()),
+            part_two: None,
+        };
+
+        assert_eq!(
+            verdicts(&answers, &[]),
+            vec![
+                Verdict::Fail {
+                    expected: "42".into(),
+                    actual: None
+                },
+                Verdict::Unsolved
+            ]
+        );
+    }
+}
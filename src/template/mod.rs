@@ -1,48 +1,506 @@
-use crate::Day;
-use std::{env, fs};
+use crate::template::runner::PartOutput;
+use crate::{Day, Part, PuzzleId, Year};
+use std::error::Error;
+use std::fmt::Display;
+use std::{fs, io, process};
 
+pub mod answers;
+pub mod aoc_cache;
 pub mod aoc_cli;
+pub mod aoc_client;
+pub mod bench_history;
+pub mod calendar;
 pub mod commands;
 pub mod readme_benchmarks;
+pub mod record;
+pub mod registry;
 pub mod runner;
+mod solution;
+pub mod submissions;
+
+pub use solution::{Solution, SolutionOutput};
+
+#[doc(hidden)]
+pub use paste::paste;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
+/// An error which can be returned when reading a data file.
+#[derive(Debug)]
+pub enum ReadFileError {
+    IO { path: String, error: io::Error },
+    Empty { path: String },
+}
+
+impl Error for ReadFileError {}
+
+impl Display for ReadFileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReadFileError::IO { path, error } => write!(f, "could not read \"{path}\": {error}"),
+            ReadFileError::Empty { path } => write!(f, "\"{path}\" is empty"),
+        }
+    }
+}
+
+/// Canonicalises the line endings of an input to `\n` and strips a leading byte order mark.
+///
+/// ```
+/// # use advent_of_code::template::normalize_input;
+/// assert_eq!(normalize_input("\u{feff}1,2\r\n3\r\n"), "1,2\n3\n");
+/// ```
+#[must_use]
+pub fn normalize_input(input: &str) -> String {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    input.replace("\r\n", "\n").replace('\r', "\n")
+}
+
+/// Removes the trailing newlines of an input, for solutions that opt in with `trim_newline`.
+#[must_use]
+pub fn trim_newline(input: &str) -> &str {
+    input.trim_end_matches('\n')
+}
+
+/// The `--input` path that reads the input from stdin.
+pub const STDIN_INPUT: &str = "-";
+
+/// Reads a text file to a normalized string, failing for files that are empty or only contain whitespace.
+fn try_read_path(path: String) -> Result<String, ReadFileError> {
+    let content = fs::read_to_string(&path);
+    normalize_content(path, content)
+}
+
+fn normalize_content(path: String, content: io::Result<String>) -> Result<String, ReadFileError> {
+    match content {
+        Ok(content) if content.trim().is_empty() => Err(ReadFileError::Empty { path }),
+        Ok(content) => Ok(normalize_input(&content)),
+        Err(error) => Err(ReadFileError::IO { path, error }),
+    }
+}
+
 /// Helper function that reads a text file to a string.
+pub fn try_read_file(folder: &str, day: Day) -> Result<String, ReadFileError> {
+    try_read_path(format!("data/{folder}/{day}.txt"))
+}
+
+/// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
+pub fn try_read_file_part(folder: &str, day: Day, part: u8) -> Result<String, ReadFileError> {
+    try_read_path(format!("data/{folder}/{day}-{part}.txt"))
+}
+
+/// Helper function that reads a text file of a puzzle of any year to a string.
+pub fn try_read_puzzle_file(folder: &str, puzzle: PuzzleId) -> Result<String, ReadFileError> {
+    try_read_path(puzzle.data_file(folder, "txt"))
+}
+
+/// Helper function that reads a text file of a puzzle of any year to string, appending a part suffix.
+pub fn try_read_puzzle_file_part(
+    folder: &str,
+    puzzle: PuzzleId,
+    part: u8,
+) -> Result<String, ReadFileError> {
+    try_read_path(format!(
+        "{}/{folder}/{}-{part}.txt",
+        puzzle.year.data_dir(),
+        puzzle.day
+    ))
+}
+
+/// Like [`try_read_file`], but panics if the file cannot be read. Meant for tests.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    try_read_file(folder, day).unwrap_or_else(|e| panic!("{e}"))
 }
 
-/// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
+/// Like [`try_read_file_part`], but panics if the file cannot be read. Meant for tests.
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    try_read_file_part(folder, day, part).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Like [`try_read_puzzle_file`], but panics if the file cannot be read. Meant for tests.
+#[must_use]
+pub fn read_puzzle_file(folder: &str, puzzle: PuzzleId) -> String {
+    try_read_puzzle_file(folder, puzzle).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Like [`try_read_puzzle_file_part`], but panics if the file cannot be read. Meant for tests.
+#[must_use]
+pub fn read_puzzle_file_part(folder: &str, puzzle: PuzzleId, part: u8) -> String {
+    try_read_puzzle_file_part(folder, puzzle, part).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Reads an example of a puzzle for [`examples!`], i.e. `NN.txt` or `NN-<suffix>.txt`, and panics if it cannot be read.
+#[must_use]
+pub fn read_example(puzzle: PuzzleId, suffix: Option<u8>) -> String {
+    let example = match suffix {
+        Some(suffix) => try_read_puzzle_file_part("examples", puzzle, suffix),
+        None => try_read_puzzle_file("examples", puzzle),
+    };
+    example.unwrap_or_else(|e| panic!("{e}, save the example there or remove it from `examples!`"))
+}
+
+/// Checks the answer of a part for an example of [`examples!`] against the answer recorded next to it,
+/// e.g. in `NN-<suffix>.toml` for `NN-<suffix>.txt`. A part without a recorded answer is expected to be unsolved.
+#[doc(hidden)]
+pub fn check_example<R: PartOutput>(
+    puzzle: PuzzleId,
+    suffix: Option<u8>,
+    part: Part,
+    solve: impl Fn(&str) -> R,
+) {
+    let example = read_example(puzzle, suffix);
+
+    let stem = match suffix {
+        Some(suffix) => format!(
+            "{}/examples/{}-{suffix}",
+            puzzle.year.data_dir(),
+            puzzle.day
+        ),
+        None => format!("{}/examples/{}", puzzle.year.data_dir(), puzzle.day),
+    };
+    let answers_path = format!("{stem}.toml");
+    let expected = answers::load_path(&answers_path)
+        .unwrap_or_else(|e| panic!("failed to load \"{answers_path}\": {e}"));
+
+    assert_eq!(
+        solve(&example).answer(),
+        Ok(expected.get(part).map(String::from)),
+        "wrong answer of part {part} for \"{stem}.txt\", its expected answer is read from \"{answers_path}\""
+    );
+}
+
+/// Reads the input of a puzzle. If it cannot be read, the reason and how to download the input are printed instead.
+#[must_use]
+pub fn read_input(puzzle: PuzzleId) -> Option<String> {
+    match try_read_puzzle_file("inputs", puzzle) {
+        Ok(input) => Some(input),
+        Err(e) => {
+            let year = if puzzle.year.is_default() {
+                String::new()
+            } else {
+                format!(" --year {}", puzzle.year)
+            };
+            eprintln!("Failed to read the input of {puzzle}: {e}.");
+            eprintln!("Type `cargo download {}{year}` to download it.", puzzle.day);
+            None
+        }
+    }
+}
+
+/// Reads the `--input` option that was passed to the solution binary.
+#[must_use]
+pub fn input_path() -> Option<String> {
+    let mut args = pico_args::Arguments::from_env();
+    match args.opt_value_from_str("--input") {
+        Ok(path) => path,
+        Err(e) => {
+            eprintln!("Invalid input path: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Reads the input a solution binary runs against: the file passed with `--input <path>`, stdin for `--input -`,
+/// or the input of the puzzle. If it cannot be read, the reason is printed instead.
+#[must_use]
+pub fn read_solution_input(puzzle: PuzzleId) -> Option<String> {
+    let Some(path) = input_path() else {
+        return read_input(puzzle);
+    };
+
+    let result = if path == STDIN_INPUT {
+        normalize_content("stdin".into(), io::read_to_string(io::stdin()))
+    } else {
+        try_read_path(path)
+    };
+
+    match result {
+        Ok(input) => Some(input),
+        Err(e) => {
+            eprintln!("Failed to read the input of {puzzle}: {e}.");
+            None
+        }
+    }
+}
+
+/// Returns the default year set by `AOC_YEAR`, and exits if it is not set.
+#[must_use]
+pub fn default_year() -> Year {
+    Year::from_env().unwrap_or_else(|| {
+        eprintln!(
+            "\"AOC_YEAR\" is not set to a valid year, it is configured in \".cargo/config.toml\"."
+        );
+        process::exit(1);
+    })
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
+/// The solution is also exposed through a `register` function for the in-process `solutions` binary.
+///
+/// Solutions of a year other than the default year pass the year as well, e.g. `solution!(5, 2022)`,
+/// which also creates the constant `PUZZLE`.
+///
+/// The day (and year) can be followed by options, e.g. `solution!(25, single_part)`:
+/// - `single_part`: the day only has one part, i.e. day 25. The solution only defines `part_one`,
+///   the second part is reported as not applicable.
+/// - `trim_newline`: the trailing newlines of the input are removed before it is passed to the parts.
+///
+/// Solutions that implement [`Solution`] pass their type after the day (and year), e.g. `solution!(14 => Dish)`.
+/// Their input is parsed once for both parts, and the parse step is timed separately.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
+    (@run_parts both_parts $trim:tt [];) => {
+        advent_of_code::solution!(@prepare_input $trim);
+
+        /// Runs both parts of the solution against an input.
+        fn run_parts(input: &str, puzzle: advent_of_code::PuzzleId) {
+            use advent_of_code::template::runner::*;
+            let input = prepare_input(input);
+            run_part(part_one, input, puzzle, $crate::Part::One);
+            run_part(part_two, input, puzzle, $crate::Part::Two);
+        }
+    };
+    (@run_parts single_part $trim:tt [];) => {
+        advent_of_code::solution!(@prepare_input $trim);
+
+        /// Runs the only part of the solution against an input.
+        fn run_parts(input: &str, puzzle: advent_of_code::PuzzleId) {
+            let input = prepare_input(input);
+            advent_of_code::template::runner::run_single_part(part_one, input, puzzle);
+        }
+    };
+    (@run_parts both_parts $trim:tt [$solution:ty];) => {
+        advent_of_code::solution!(@prepare_input $trim);
+
+        /// Solves the first part from the raw input, see [`advent_of_code::template::Solution`].
+        #[allow(dead_code)]
+        pub fn part_one(
+            input: &str,
+        ) -> advent_of_code::template::SolutionOutput<
+            <$solution as advent_of_code::template::Solution>::OutputOne,
+        > {
+            use advent_of_code::template::Solution;
+            advent_of_code::template::SolutionOutput(
+                <$solution>::parse(input)
+                    .map(|parsed| <$solution>::part_one(&parsed))
+                    .map_err(|e| e.to_string()),
+            )
+        }
+
+        /// Solves the second part from the raw input, see [`advent_of_code::template::Solution`].
+        #[allow(dead_code)]
+        pub fn part_two(
+            input: &str,
+        ) -> advent_of_code::template::SolutionOutput<
+            <$solution as advent_of_code::template::Solution>::OutputTwo,
+        > {
+            use advent_of_code::template::Solution;
+            advent_of_code::template::SolutionOutput(
+                <$solution>::parse(input)
+                    .map(|parsed| <$solution>::part_two(&parsed))
+                    .map_err(|e| e.to_string()),
+            )
+        }
+
+        /// Runs the solution against an input, which is parsed once for both parts.
+        fn run_parts(input: &str, puzzle: advent_of_code::PuzzleId) {
+            let input = prepare_input(input);
+            advent_of_code::template::runner::run_solution::<$solution>(input, puzzle);
+        }
+    };
+    (@run_parts single_part $trim:tt [$solution:ty];) => {
+        compile_error!("solutions with a `single_part` are written as functions, not as a `Solution`");
+    };
+    (@run_parts $parts:ident $trim:tt $solution:tt; single_part $($option:ident)*) => {
+        advent_of_code::solution!(@run_parts single_part $trim $solution; $($option)*);
+    };
+    (@run_parts $parts:ident $trim:tt $solution:tt; trim_newline $($option:ident)*) => {
+        advent_of_code::solution!(@run_parts $parts true $solution; $($option)*);
+    };
+    (@run_parts $parts:ident $trim:tt $solution:tt; $unknown:ident $($option:ident)*) => {
+        compile_error!(concat!(
+            "unknown solution option `",
+            stringify!($unknown),
+            "`, expecting `single_part` or `trim_newline`"
+        ));
+    };
+    (@prepare_input true) => {
+        /// Prepares an input before it is passed to the parts, the solution opted in to `trim_newline`.
+        fn prepare_input(input: &str) -> &str {
+            advent_of_code::template::trim_newline(input)
+        }
+    };
+    (@prepare_input false) => {
+        /// Prepares an input before it is passed to the parts.
+        fn prepare_input(input: &str) -> &str {
+            input
+        }
+    };
+    (@default $day:expr; $solution:tt $($option:ident)*) => {
         /// The current day.
         const DAY: advent_of_code::Day = advent_of_code::day!($day);
 
+        advent_of_code::solution!(@run_parts both_parts false $solution; $($option)*);
+
+        /// The current puzzle, of the default year.
+        fn puzzle() -> advent_of_code::PuzzleId {
+            advent_of_code::PuzzleId::new(advent_of_code::template::default_year(), DAY)
+        }
+
+        /// Adds the solution to a registry, which allows running it in-process with other days.
+        #[allow(dead_code)]
+        pub fn register(registry: &mut advent_of_code::template::registry::Registry) {
+            registry.register(DAY, run_parts);
+        }
+
         fn main() {
-            use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", DAY);
-            run_part(part_one, &input, DAY, 1);
-            run_part(part_two, &input, DAY, 2);
+            let puzzle = puzzle();
+            let Some(input) = advent_of_code::template::read_solution_input(puzzle) else {
+                std::process::exit(1);
+            };
+            run_parts(&input, puzzle);
         }
     };
+    (@year $day:expr, $year:expr; $solution:tt $($option:ident)*) => {
+        /// The current day.
+        const DAY: advent_of_code::Day = advent_of_code::day!($day);
+
+        /// The current puzzle.
+        const PUZZLE: advent_of_code::PuzzleId =
+            advent_of_code::PuzzleId::new(advent_of_code::year!($year), DAY);
+
+        advent_of_code::solution!(@run_parts both_parts false $solution; $($option)*);
+
+        /// The current puzzle.
+        #[allow(dead_code)]
+        fn puzzle() -> advent_of_code::PuzzleId {
+            PUZZLE
+        }
+
+        /// Adds the solution to a registry, which allows running it in-process with other days.
+        #[allow(dead_code)]
+        pub fn register(registry: &mut advent_of_code::template::registry::Registry) {
+            registry.register_puzzle(PUZZLE, run_parts);
+        }
+
+        fn main() {
+            let Some(input) = advent_of_code::template::read_solution_input(PUZZLE) else {
+                std::process::exit(1);
+            };
+            run_parts(&input, PUZZLE);
+        }
+    };
+    ($day:expr => $solution:ty $(, $option:ident)*) => {
+        advent_of_code::solution!(@default $day; [$solution] $($option)*);
+    };
+    ($day:expr, $year:expr => $solution:ty $(, $option:ident)*) => {
+        advent_of_code::solution!(@year $day, $year; [$solution] $($option)*);
+    };
+    ($day:expr $(, $option:ident)*) => {
+        advent_of_code::solution!(@default $day; [] $($option)*);
+    };
+    ($day:expr, $year:expr $(, $option:ident)*) => {
+        advent_of_code::solution!(@year $day, $year; [] $($option)*);
+    };
+}
+
+/// Creates a test for every example of a part, which checks the answer of the part for that example.
+/// A part on its own is tested against `data/examples/NN.txt`, a part with a list of suffixes against
+/// `data/examples/NN-<suffix>.txt` for each suffix. The expected answers are read from the `.toml` file next to
+/// each example, e.g. `NN-<suffix>.toml`, which `solve --examples` shows as well.
+/// The examples are read and prepared like the input, and a missing example fails its test with the path of the file.
+///
+/// ```ignore
+/// advent_of_code::examples! {
+///     part_one,
+///     part_two: [1, 2],
+/// }
+/// ```
+///
+/// The tests are named after the part and the suffix, e.g. `examples::part_one` for `NN.txt` and `examples::part_two_2`.
+#[macro_export]
+macro_rules! examples {
+    (@part part_one) => {
+        advent_of_code::Part::One
+    };
+    (@part part_two) => {
+        advent_of_code::Part::Two
+    };
+    (@test $part:ident, $name:ident, $suffix:expr) => {
+        #[test]
+        fn $name() {
+            advent_of_code::template::check_example(
+                super::puzzle(),
+                $suffix,
+                advent_of_code::examples!(@part $part),
+                |example| super::$part(super::prepare_input(example)),
+            );
+        }
+    };
+    (@parts) => {};
+    (@parts $part:ident: [$($suffix:literal),* $(,)?] $(, $($rest:tt)*)?) => {
+        $(advent_of_code::template::paste! {
+            advent_of_code::examples!(@test $part, [<$part _ $suffix>], Some($suffix));
+        })*
+        advent_of_code::examples!(@parts $($($rest)*)?);
+    };
+    (@parts $part:ident $(, $($rest:tt)*)?) => {
+        advent_of_code::examples!(@test $part, $part, None);
+        advent_of_code::examples!(@parts $($($rest)*)?);
+    };
+    ($($parts:tt)*) => {
+        #[cfg(test)]
+        mod examples {
+            advent_of_code::examples!(@parts $($parts)*);
+        }
+    };
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{normalize_input, trim_newline, try_read_path, ReadFileError};
+    use std::{env, fs};
+
+    #[test]
+    fn names_path_of_unreadable_files() {
+        let missing = env::temp_dir().join("aoc-missing-input.txt");
+        let path = missing.to_string_lossy().to_string();
+
+        let e = try_read_path(path.clone()).unwrap_err();
+        assert!(matches!(e, ReadFileError::IO { .. }));
+        assert!(e.to_string().contains(&path));
+    }
+
+    #[test]
+    fn rejects_empty_files() {
+        let empty = env::temp_dir().join("aoc-empty-input.txt");
+        fs::write(&empty, "\n").unwrap();
+        let path = empty.to_string_lossy().to_string();
+
+        let e = try_read_path(path.clone()).unwrap_err();
+        fs::remove_file(&empty).unwrap();
+        assert_eq!(e.to_string(), format!("\"{path}\" is empty"));
+    }
+
+    #[test]
+    fn normalizes_input() {
+        let crlf = env::temp_dir().join("aoc-crlf-input.txt");
+        fs::write(&crlf, "\u{feff}a\r\n\r\nb\r\n").unwrap();
+        let content = try_read_path(crlf.to_string_lossy().to_string()).unwrap();
+        fs::remove_file(&crlf).unwrap();
+
+        assert_eq!(content, "a\n\nb\n");
+        assert_eq!(normalize_input("a\rb"), "a\nb");
+        assert_eq!(normalize_input("a\u{feff}"), "a\u{feff}");
+    }
+
+    #[test]
+    fn trims_trailing_newlines() {
+        assert_eq!(trim_newline("1,2\n"), "1,2");
+        assert_eq!(trim_newline("a\n\nb\n\n"), "a\n\nb");
+        assert_eq!(trim_newline(" a "), " a ");
+    }
 }
@@ -2,7 +2,8 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
-use crate::Day;
+use crate::template::runner::Statistic;
+use crate::{Day, Year};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -21,6 +22,8 @@ impl From<std::io::Error> for Error {
 #[derive(Clone)]
 pub struct Timings {
     pub day: Day,
+    /// The parse step of solutions that implement [`crate::template::Solution`].
+    pub parse: Option<String>,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
     pub total_nanos: f64,
@@ -58,71 +61,115 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> String {
+fn construct_table(
+    prefix: &str,
+    timings: Vec<Timings>,
+    total_millis: f64,
+    statistic: Statistic,
+) -> String {
     let header = format!("{prefix} Benchmarks");
 
     let mut lines: Vec<String> = vec![
         MARKER.into(),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        "| Day | Parse | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: | :---:  |".into(),
     ];
 
     for timing in timings {
         let path = get_path_for_bin(timing.day);
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) | `{}` | `{}` | `{}` |",
             timing.day.into_inner(),
             path,
+            timing.parse.unwrap_or_else(|| "-".into()),
             timing.part_1.unwrap_or_else(|| "-".into()),
             timing.part_2.unwrap_or_else(|| "-".into())
         ));
     }
 
     lines.push(String::new());
-    lines.push(format!("**Total: {total_millis:.2}ms**"));
+    match statistic {
+        Statistic::Mean => lines.push(format!("**Total: {total_millis:.2}ms**")),
+        Statistic::Median => lines.push(format!("**Total: {total_millis:.2}ms** (median)")),
+    }
     lines.push(MARKER.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
+fn update_content(
+    s: &mut String,
+    timings: Vec<Timings>,
+    total_millis: f64,
+    statistic: Statistic,
+) -> Result<(), Error> {
     let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+    let table = construct_table("##", timings, total_millis, statistic);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
+/// Removes the table from the readme and returns it with its links pointing to the archived solutions of `year`.
+/// The links are relative to `data/<year>/`, where the table is archived.
+fn take_table(s: &mut String, year: Year) -> Result<String, Error> {
+    let positions = locate_table(s)?;
+    let table = s[positions.pos_start..positions.pos_end]
+        .replace(MARKER, "")
+        .replace("## Benchmarks", &format!("## Benchmarks {year}"))
+        .replace("](./src/bin/", &format!("](../../src/bin/{year}-"));
+    s.replace_range(
+        positions.pos_start..positions.pos_end,
+        &format!("{MARKER}\n{MARKER}"),
+    );
+    Ok(format!("{}\n", table.trim()))
+}
+
+/// Moves the benchmark table of the readme to `data/<year>/benchmarks.md`.
+pub fn archive(year: Year) -> Result<(), Error> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    let table = take_table(&mut readme, year)?;
+    fs::create_dir_all(format!("data/{year}"))?;
+    fs::write(format!("data/{year}/benchmarks.md"), table)?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+pub fn update(timings: Vec<Timings>, total_millis: f64, statistic: Statistic) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, timings, total_millis, statistic)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, Timings, MARKER};
-    use crate::day;
+    use super::{take_table, update_content, Timings, MARKER};
+    use crate::template::runner::Statistic;
+    use crate::{day, year};
 
     fn get_mock_timings() -> Vec<Timings> {
         vec![
             Timings {
                 day: day!(1),
+                parse: None,
                 part_1: Some("10ms".into()),
                 part_2: Some("20ms".into()),
                 total_nanos: 3e+10,
             },
             Timings {
                 day: day!(2),
+                parse: Some("5ms".into()),
                 part_1: Some("30ms".into()),
                 part_2: Some("40ms".into()),
                 total_nanos: 7e+10,
             },
             Timings {
                 day: day!(4),
+                parse: None,
                 part_1: Some("40ms".into()),
                 part_2: Some("50ms".into()),
                 total_nanos: 9e+10,
@@ -134,28 +181,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, Statistic::Mean).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, Statistic::Mean).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, Statistic::Mean).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, Statistic::Mean).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, Statistic::Mean).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -163,18 +210,18 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, Statistic::Mean).unwrap();
         let expected = [
             "foo",
             "bar",
             "<!--- benchmarking table --->",
             "## Benchmarks",
             "",
-            "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| Day | Parse | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/01.rs) | `-` | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/02.rs) | `5ms` | `30ms` | `40ms` |",
+            "| [Day 4](./src/bin/04.rs) | `-` | `40ms` | `50ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -183,4 +230,23 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_median_benchmarks() {
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, get_mock_timings(), 190.0, Statistic::Median).unwrap();
+        assert_eq!(s.contains("**Total: 190.00ms** (median)"), true);
+    }
+
+    #[test]
+    fn takes_table_for_archive() {
+        let mut s = format!("foo\n{}{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, get_mock_timings(), 190.0, Statistic::Mean).unwrap();
+
+        let table = take_table(&mut s, year!(2023)).unwrap();
+        assert_eq!(s, format!("foo\n{}\n{}\nbaz", MARKER, MARKER));
+        assert!(table.starts_with("## Benchmarks 2023\n"));
+        assert!(table.contains("| [Day 2](../../src/bin/2023-02.rs) | `5ms` | `30ms` | `40ms` |"));
+        assert!(!table.contains(MARKER));
+    }
 }
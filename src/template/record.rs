@@ -0,0 +1,368 @@
+/// Machine-readable results of solution parts.
+/// Solution binaries emit one record per line on stdout when invoked with `--format json`.
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::time::Duration;
+
+use tinyjson::JsonValue;
+
+use crate::template::runner::BenchStats;
+use crate::{Day, Part};
+
+/// The format in which a solution binary reports its results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    #[default]
+    Human,
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = OutputFormatFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(Self::Human),
+            "json" => Ok(Self::Json),
+            _ => Err(OutputFormatFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing an [`OutputFormat`].
+#[derive(Debug)]
+pub struct OutputFormatFromStrError;
+
+impl Error for OutputFormatFromStrError {}
+
+impl Display for OutputFormatFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting one of `human` or `json`")
+    }
+}
+
+/// The outcome of running a solution part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartStatus {
+    /// The part returned an answer.
+    Solved,
+    /// The part returned `None`.
+    Unsolved,
+    /// The day has no such part, e.g. the second part of day 25.
+    NotApplicable,
+    /// The part returned an error.
+    Failed,
+}
+
+impl PartStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            PartStatus::Solved => "solved",
+            PartStatus::Unsolved => "unsolved",
+            PartStatus::NotApplicable => "not_applicable",
+            PartStatus::Failed => "failed",
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "solved" => Some(PartStatus::Solved),
+            "unsolved" => Some(PartStatus::Unsolved),
+            "not_applicable" => Some(PartStatus::NotApplicable),
+            "failed" => Some(PartStatus::Failed),
+            _ => None,
+        }
+    }
+}
+
+/// The result and timing of a single solution part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartRecord {
+    pub day: Day,
+    pub part: Part,
+    pub status: PartStatus,
+    pub answer: Option<String>,
+    /// The message of the error a failed part returned.
+    pub error: Option<String>,
+    pub stats: BenchStats,
+}
+
+impl PartRecord {
+    /// Serializes the record as a single line of JSON.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let fields = [
+            ("day", JsonValue::Number(f64::from(self.day.into_inner()))),
+            ("part", JsonValue::Number(f64::from(self.part.into_inner()))),
+            ("status", JsonValue::String(self.status.as_str().into())),
+            (
+                "answer",
+                self.answer
+                    .clone()
+                    .map_or(JsonValue::Null, JsonValue::String),
+            ),
+            (
+                "error",
+                self.error
+                    .clone()
+                    .map_or(JsonValue::Null, JsonValue::String),
+            ),
+        ]
+        .into_iter()
+        .chain(stats_to_json(&self.stats));
+
+        JsonValue::Object(
+            fields
+                .map(|(key, value)| (key.to_string(), value))
+                .collect(),
+        )
+        .stringify()
+        .expect("records only contain finite numbers")
+    }
+
+    /// Parses a line emitted by [`PartRecord::to_json`].
+    /// Returns [`None`] if the line is not a record, e.g. because a solution printed to stdout.
+    #[must_use]
+    pub fn from_json(line: &str) -> Option<Self> {
+        let JsonValue::Object(fields) = line.parse::<JsonValue>().ok()? else {
+            return None;
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let number = |key: &str| match fields.get(key)? {
+            JsonValue::Number(n) => Some(*n as u64),
+            _ => None,
+        };
+
+        let status = match fields.get("status")? {
+            JsonValue::String(s) => PartStatus::parse(s)?,
+            _ => return None,
+        };
+
+        let string = |key: &str| match fields.get(key)? {
+            JsonValue::String(s) => Some(Some(s.clone())),
+            JsonValue::Null => Some(None),
+            _ => None,
+        };
+
+        Some(Self {
+            day: Day::new(u8::try_from(number("day")?).ok()?)?,
+            part: Part::new(u8::try_from(number("part")?).ok()?)?,
+            status,
+            answer: string("answer")?,
+            error: string("error")?,
+            stats: stats_from_json(number)?,
+        })
+    }
+}
+
+/// The timing of the parse step of a solution that implements [`crate::template::Solution`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseRecord {
+    pub day: Day,
+    pub stats: BenchStats,
+}
+
+impl ParseRecord {
+    /// Serializes the record as a single line of JSON.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let fields = [
+            ("event", JsonValue::String("parse".into())),
+            ("day", JsonValue::Number(f64::from(self.day.into_inner()))),
+        ]
+        .into_iter()
+        .chain(stats_to_json(&self.stats));
+
+        JsonValue::Object(
+            fields
+                .map(|(key, value)| (key.to_string(), value))
+                .collect(),
+        )
+        .stringify()
+        .expect("records only contain finite numbers")
+    }
+
+    /// Parses a line emitted by [`ParseRecord::to_json`].
+    #[must_use]
+    pub fn from_json(line: &str) -> Option<Self> {
+        let JsonValue::Object(fields) = line.parse::<JsonValue>().ok()? else {
+            return None;
+        };
+
+        if fields.get("event")? != &JsonValue::String("parse".into()) {
+            return None;
+        }
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let number = |key: &str| match fields.get(key)? {
+            JsonValue::Number(n) => Some(*n as u64),
+            _ => None,
+        };
+
+        Some(Self {
+            day: Day::new(u8::try_from(number("day")?).ok()?)?,
+            stats: stats_from_json(number)?,
+        })
+    }
+}
+
+fn stats_to_json(stats: &BenchStats) -> [(&'static str, JsonValue); 8] {
+    #[allow(clippy::cast_precision_loss)]
+    let nanos = |d: Duration| JsonValue::Number(d.as_nanos() as f64);
+
+    #[allow(clippy::cast_precision_loss)]
+    [
+        ("duration_nanos", nanos(stats.mean)),
+        ("samples", JsonValue::Number(stats.samples as f64)),
+        ("outliers", JsonValue::Number(stats.outliers as f64)),
+        ("min_nanos", nanos(stats.min)),
+        ("median_nanos", nanos(stats.median)),
+        ("p95_nanos", nanos(stats.p95)),
+        ("max_nanos", nanos(stats.max)),
+        ("std_dev_nanos", nanos(stats.std_dev)),
+    ]
+}
+
+fn stats_from_json(number: impl Fn(&str) -> Option<u64>) -> Option<BenchStats> {
+    let nanos = |key: &str| number(key).map(Duration::from_nanos);
+
+    Some(BenchStats {
+        samples: u128::from(number("samples")?),
+        outliers: u128::from(number("outliers")?),
+        mean: nanos("duration_nanos")?,
+        min: nanos("min_nanos")?,
+        median: nanos("median_nanos")?,
+        p95: nanos("p95_nanos")?,
+        max: nanos("max_nanos")?,
+        std_dev: nanos("std_dev_nanos")?,
+    })
+}
+
+/// Marks the start of a day's records when a single binary runs several days.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DayMarker(pub Day);
+
+impl DayMarker {
+    /// Serializes the marker as a single line of JSON.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        JsonValue::Object(
+            [
+                ("event".to_string(), JsonValue::String("day".into())),
+                (
+                    "day".to_string(),
+                    JsonValue::Number(f64::from(self.0.into_inner())),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        )
+        .stringify()
+        .expect("markers only contain finite numbers")
+    }
+
+    /// Parses a line emitted by [`DayMarker::to_json`].
+    #[must_use]
+    pub fn from_json(line: &str) -> Option<Self> {
+        let JsonValue::Object(fields) = line.parse::<JsonValue>().ok()? else {
+            return None;
+        };
+
+        if fields.get("event")? != &JsonValue::String("day".into()) {
+            return None;
+        }
+
+        match fields.get("day")? {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            JsonValue::Number(n) => Day::new(*n as u8).map(Self),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{DayMarker, ParseRecord, PartRecord, PartStatus};
+    use crate::template::runner::BenchStats;
+    use crate::{day, Part};
+    use std::time::Duration;
+
+    fn record(answer: Option<&str>) -> PartRecord {
+        PartRecord {
+            day: day!(7),
+            part: Part::Two,
+            status: if answer.is_some() {
+                PartStatus::Solved
+            } else {
+                PartStatus::Unsolved
+            },
+            answer: answer.map(String::from),
+            error: None,
+            stats: BenchStats::from_samples(&[
+                Duration::from_nanos(70),
+                Duration::from_nanos(90),
+                Duration::from_nanos(80),
+            ]),
+        }
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let record = record(Some("42"));
+        assert_eq!(PartRecord::from_json(&record.to_json()), Some(record));
+    }
+
+    #[test]
+    fn round_trips_unsolved_part() {
+        let record = record(None);
+        assert_eq!(PartRecord::from_json(&record.to_json()), Some(record));
+    }
+
+    #[test]
+    fn round_trips_failed_part() {
+        let mut record = record(None);
+        record.status = PartStatus::Failed;
+        record.error = Some("unexpected card 'X'".into());
+        assert_eq!(PartRecord::from_json(&record.to_json()), Some(record));
+    }
+
+    #[test]
+    fn round_trips_answers_with_special_characters() {
+        let record = record(Some("@ ( ) ms (2s @ 5 samples)\n\"#.#\""));
+        assert_eq!(PartRecord::from_json(&record.to_json()), Some(record));
+    }
+
+    #[test]
+    fn ignores_other_output() {
+        assert_eq!(
+            PartRecord::from_json("Part 1: 0 (74.13ns @ 100000 samples)"),
+            None
+        );
+        assert_eq!(PartRecord::from_json("{\"foo\": 1}"), None);
+        assert_eq!(PartRecord::from_json("[1, 2]"), None);
+    }
+
+    #[test]
+    fn day_markers_are_distinct_from_records() {
+        let marker = DayMarker(day!(7)).to_json();
+        assert_eq!(DayMarker::from_json(&marker), Some(DayMarker(day!(7))));
+        assert_eq!(PartRecord::from_json(&marker), None);
+        assert_eq!(DayMarker::from_json(&record(Some("42")).to_json()), None);
+    }
+
+    #[test]
+    fn round_trips_parse_record() {
+        let record = ParseRecord {
+            day: day!(14),
+            stats: record(None).stats,
+        };
+        let json = record.to_json();
+
+        assert_eq!(ParseRecord::from_json(&json), Some(record));
+        assert_eq!(PartRecord::from_json(&json), None);
+        assert_eq!(DayMarker::from_json(&json), None);
+        assert_eq!(ParseRecord::from_json(&DayMarker(day!(14)).to_json()), None);
+    }
+}
@@ -0,0 +1,146 @@
+/// A collection of solutions that can be run within a single process.
+/// The `solutions` binary compiles every scaffolded day into one executable and registers it here.
+use std::collections::BTreeMap;
+use std::panic;
+use std::process;
+
+use crate::template::record::{DayMarker, OutputFormat};
+use crate::template::{read_input, ANSI_BOLD, ANSI_RESET};
+use crate::{Day, PuzzleId, Year};
+
+/// Runs all parts of a solution against an input, as generated by the `solution!` macro.
+pub type SolutionFn = fn(&str, PuzzleId);
+
+pub struct Registry {
+    default_year: Year,
+    solutions: BTreeMap<PuzzleId, SolutionFn>,
+}
+
+impl Registry {
+    /// Creates an empty registry. Solutions registered by day belong to the `default_year`.
+    #[must_use]
+    pub fn new(default_year: Year) -> Self {
+        Self {
+            default_year,
+            solutions: BTreeMap::new(),
+        }
+    }
+
+    /// Registers the solution for a day of the default year, replacing any previously registered solution.
+    pub fn register(&mut self, day: Day, solution: SolutionFn) {
+        self.register_puzzle(PuzzleId::new(self.default_year, day), solution);
+    }
+
+    /// Registers the solution for a puzzle, replacing any previously registered solution.
+    pub fn register_puzzle(&mut self, puzzle: PuzzleId, solution: SolutionFn) {
+        self.solutions.insert(puzzle, solution);
+    }
+
+    /// Returns the solution for a puzzle if it has been registered.
+    #[must_use]
+    pub fn get(&self, puzzle: PuzzleId) -> Option<SolutionFn> {
+        self.solutions.get(&puzzle).copied()
+    }
+
+    /// An iterator over all registered days of a year in ascending order.
+    pub fn days(&self, year: Year) -> impl Iterator<Item = Day> + '_ {
+        self.solutions
+            .keys()
+            .filter(move |puzzle| puzzle.year == year)
+            .map(|puzzle| puzzle.day)
+    }
+
+    /// Runs the solutions of the given days of a year against their inputs in order, skipping days that are not registered.
+    /// A panicking solution does not prevent the remaining days from running.
+    pub fn run(&self, year: Year, days: &[Day], format: OutputFormat) {
+        for (i, &day) in days.iter().enumerate() {
+            let puzzle = PuzzleId::new(year, day);
+
+            let Some(solution) = self.get(puzzle) else {
+                continue;
+            };
+
+            match format {
+                OutputFormat::Human => {
+                    if i > 0 {
+                        println!();
+                    }
+                    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+                    println!("------");
+                }
+                OutputFormat::Json => println!("{}", DayMarker(day).to_json()),
+            }
+
+            let Some(input) = read_input(puzzle) else {
+                continue;
+            };
+
+            // the panic message is printed to stderr by the default panic hook.
+            let _ = panic::catch_unwind(|| solution(&input, puzzle));
+        }
+    }
+
+    /// Entry point of the `solutions` binary: runs every day passed via `--day <day>`, or all registered days
+    /// of the year passed via `--year <year>`, which defaults to the default year.
+    pub fn main(&self) {
+        let mut args = pico_args::Arguments::from_env();
+
+        let parsed = args
+            .values_from_str::<_, Day>("--day")
+            .map_err(|e| e.to_string())
+            .and_then(|days| {
+                let year = args
+                    .opt_value_from_str("--year")
+                    .map_err(|e| e.to_string())?;
+                let format = args
+                    .opt_value_from_str("--format")
+                    .map_err(|e| e.to_string())?;
+                Ok((
+                    days,
+                    year.unwrap_or(self.default_year),
+                    format.unwrap_or_default(),
+                ))
+            });
+
+        let (mut days, year, format) = match parsed {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                eprintln!("Error: {e}");
+                process::exit(1);
+            }
+        };
+
+        if days.is_empty() {
+            days = self.days(year).collect();
+        }
+
+        self.run(year, &days, format);
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Registry;
+    use crate::{day, year, PuzzleId};
+
+    fn noop(_: &str, _: PuzzleId) {}
+
+    #[test]
+    fn yields_registered_days_in_order() {
+        let mut registry = Registry::new(year!(2023));
+        registry.register(day!(12), noop);
+        registry.register(day!(3), noop);
+        registry.register_puzzle(PuzzleId::new(year!(2022), day!(1)), noop);
+
+        assert_eq!(
+            registry.days(year!(2023)).collect::<Vec<_>>(),
+            vec![day!(3), day!(12)]
+        );
+        assert_eq!(
+            registry.days(year!(2022)).collect::<Vec<_>>(),
+            vec![day!(1)]
+        );
+        assert!(registry.get(PuzzleId::new(year!(2023), day!(3))).is_some());
+        assert!(registry.get(PuzzleId::new(year!(2023), day!(4))).is_none());
+    }
+}
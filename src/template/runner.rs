@@ -1,63 +1,240 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
-use crate::Day;
+use crate::template::aoc_cli::{AocCommandError, Backend, SubmitResponse};
+use crate::template::record::{OutputFormat, ParseRecord, PartRecord, PartStatus};
+use crate::template::submissions::{Cooldown, Ledger, Submission};
+use crate::template::{aoc_cli, input_path, Solution, ANSI_ITALIC, ANSI_RESET};
+use crate::{Part, PuzzleId};
+use std::error::Error;
 use std::fmt::Display;
 use std::io::{stdout, Write};
-use std::process::Output;
-use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::str::FromStr;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use std::{cmp, process};
 
 use super::ANSI_BOLD;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Indentation that marks the line with the spread of benchmark samples below a part.
+const STATS_PREFIX: &str = "   > ";
+
+/// The value returned by a solution part: an [`Option`] that is [`None`] while the part is not solved,
+/// or a [`Result`] for parts that can fail with an error.
+pub trait PartOutput {
+    /// Returns the answer of the part, [`None`] if it is not solved, or the message of its error.
+    fn answer(&self) -> Result<Option<String>, String>;
+}
+
+impl<T: Display> PartOutput for Option<T> {
+    fn answer(&self) -> Result<Option<String>, String> {
+        Ok(self.as_ref().map(ToString::to_string))
+    }
+}
+
+impl<T: Display, E: Display> PartOutput for Result<T, E> {
+    fn answer(&self) -> Result<Option<String>, String> {
+        match self {
+            Ok(answer) => Ok(Some(answer.to_string())),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+}
+
+pub fn run_part<I: Clone, R: PartOutput>(
+    func: impl Fn(I) -> R,
+    input: I,
+    puzzle: PuzzleId,
+    part: Part,
+) {
     let part_str = format!("Part {part}");
+    let format = output_format();
+
+    let (result, stats) = run_timed(func, input, format, |result| {
+        if format == OutputFormat::Human {
+            print_result(&result.answer(), &part_str, "");
+        }
+    });
+
+    let answer = result.answer();
+
+    match format {
+        OutputFormat::Human => print_result(&answer, &part_str, &format_duration(&stats)),
+        OutputFormat::Json => {
+            let record = PartRecord {
+                day: puzzle.day,
+                part,
+                status: match answer {
+                    Ok(Some(_)) => PartStatus::Solved,
+                    Ok(None) => PartStatus::Unsolved,
+                    Err(_) => PartStatus::Failed,
+                },
+                answer: answer.clone().ok().flatten(),
+                error: answer.clone().err(),
+                stats,
+            };
+            println!("{}", record.to_json());
+        }
+    }
+
+    if let Ok(Some(answer)) = answer {
+        submit_result(answer, puzzle, part);
+    }
+}
+
+/// Runs the only part of a solution that has a single part, e.g. day 25.
+/// The second part is reported as not applicable and cannot be submitted.
+pub fn run_single_part<I: Clone, R: PartOutput>(func: impl Fn(I) -> R, input: I, puzzle: PuzzleId) {
+    if submit_part() == Some(Part::Two) {
+        eprintln!("Day {} has no second part to submit.", puzzle.day);
+        process::exit(1);
+    }
+
+    run_part(func, input, puzzle, Part::One);
+
+    let record = PartRecord {
+        day: puzzle.day,
+        part: Part::Two,
+        status: PartStatus::NotApplicable,
+        answer: None,
+        error: None,
+        stats: BenchStats::from_samples(&[Duration::ZERO]),
+    };
+
+    match output_format() {
+        OutputFormat::Human => print_record(&record),
+        OutputFormat::Json => println!("{}", record.to_json()),
+    }
+}
+
+/// Runs a solution that implements [`Solution`]. The input is parsed once, and the parse step is timed and
+/// benched separately from the parts, which run against the parsed input.
+pub fn run_solution<S: Solution>(input: &str, puzzle: PuzzleId) {
+    let format = output_format();
+
+    if format == OutputFormat::Human {
+        print!("Parse:");
+        let _ = stdout().flush();
+    }
+
+    let (parsed, stats) = run_timed(S::parse, input, format, |_| {});
+    let record = ParseRecord {
+        day: puzzle.day,
+        stats,
+    };
+
+    match format {
+        OutputFormat::Human => {
+            print!("\r");
+            print_parse_record(&record);
+        }
+        OutputFormat::Json => println!("{}", record.to_json()),
+    }
+
+    match parsed {
+        Ok(parsed) => {
+            run_part(S::part_one, &parsed, puzzle, Part::One);
+            run_part(S::part_two, &parsed, puzzle, Part::Two);
+        }
+        Err(e) => {
+            // neither part can run without the parsed input, so both fail with the error of the parse.
+            for part in [Part::One, Part::Two] {
+                let record = PartRecord {
+                    day: puzzle.day,
+                    part,
+                    status: PartStatus::Failed,
+                    answer: None,
+                    error: Some(e.to_string()),
+                    stats: BenchStats::from_samples(&[Duration::ZERO]),
+                };
+
+                match format {
+                    OutputFormat::Human => print_record(&record),
+                    OutputFormat::Json => println!("{}", record.to_json()),
+                }
+            }
+        }
+    }
+}
+
+/// Prints the timing of a parse step in the same way the solution would have printed it.
+pub fn print_parse_record(record: &ParseRecord) {
+    println!("Parse:{}", format_duration(&record.stats));
+}
+
+/// Prints a record that was emitted by a solution binary in the same way the solution would have printed it.
+pub fn print_record(record: &PartRecord) {
+    if record.status == PartStatus::NotApplicable {
+        println!("Part {}: n/a", record.part);
+        return;
+    }
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let answer = match &record.error {
+        Some(error) => Err(error.clone()),
+        None => Ok(record.answer.clone()),
+    };
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    print_result(
+        &answer,
+        &format!("Part {}", record.part),
+        &format_duration(&record.stats),
+    );
+}
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+/// Reads the `--format` option that was passed to the solution binary.
+fn output_format() -> OutputFormat {
+    let mut args = pico_args::Arguments::from_env();
+    match args.opt_value_from_str("--format") {
+        Ok(format) => format.unwrap_or_default(),
+        Err(e) => {
+            eprintln!("Invalid output format: {e}");
+            process::exit(1);
+        }
     }
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///  2. in release, the function is benched according to the [`BenchConfig`] passed on the command-line.
+///
+/// The first execution is never part of the benchmark samples, it only serves to print the result early
+/// and to estimate how many samples fit into the time budget.
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    format: OutputFormat,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, BenchStats) {
     let timer = Instant::now();
     let result = func(input.clone());
     let base_time = timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    let stats = if std::env::args().any(|x| x == "--time") {
+        bench(func, input, &base_time, &BenchConfig::from_env(), format)
     } else {
-        (base_time, 1)
+        BenchStats::from_samples(&[base_time])
     };
 
-    (result, run.0, run.1)
+    (result, stats)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    config: &BenchConfig,
+    format: OutputFormat,
+) -> BenchStats {
+    if format == OutputFormat::Human {
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout().flush();
+    }
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+    for _ in 0..config.warmup {
+        func(input.clone());
+    }
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    let bench_iterations = (config.budget.as_nanos() / cmp::max(base_time.as_nanos(), 10))
+        .clamp(config.min_samples, config.max_samples);
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -69,35 +246,280 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    if config.reject_outliers {
+        BenchStats::from_samples_without_outliers(&timers)
+    } else {
+        BenchStats::from_samples(&timers)
+    }
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+/// Controls how long and how often a solution part is executed when benching.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// Approximate time spent on collecting samples.
+    pub budget: Duration,
+    /// Lower bound for the number of samples, takes precedence over the time budget.
+    pub min_samples: u128,
+    /// Upper bound for the number of samples, takes precedence over the time budget.
+    pub max_samples: u128,
+    /// Number of untimed executions before samples are collected.
+    pub warmup: u128,
+    /// Whether samples outside of the Tukey fences are discarded before computing statistics.
+    pub reject_outliers: bool,
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
-    } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            budget: Duration::from_secs(1),
+            min_samples: 10,
+            max_samples: 10000,
+            warmup: 0,
+            reject_outliers: false,
+        }
+    }
+}
+
+impl BenchConfig {
+    /// Consumes the benchmark options (`--bench-time <seconds>`, `--min-samples <n>`, `--max-samples <n>`,
+    /// `--warmup <n>` and `--reject-outliers`) from the arguments.
+    pub fn parse(args: &mut pico_args::Arguments) -> Result<Self, Box<dyn Error>> {
+        let default = Self::default();
+
+        let budget = match args.opt_value_from_str::<_, f64>("--bench-time")? {
+            Some(secs) => Duration::try_from_secs_f64(secs)
+                .map_err(|_| "--bench-time expects a non-negative number of seconds")?,
+            None => default.budget,
+        };
+
+        let config = Self {
+            budget,
+            min_samples: args
+                .opt_value_from_str("--min-samples")?
+                .unwrap_or(default.min_samples),
+            max_samples: args
+                .opt_value_from_str("--max-samples")?
+                .unwrap_or(default.max_samples),
+            warmup: args
+                .opt_value_from_str("--warmup")?
+                .unwrap_or(default.warmup),
+            reject_outliers: args.contains("--reject-outliers"),
+        };
+
+        if config.min_samples == 0 {
+            return Err("--min-samples must be at least 1".into());
+        }
+
+        if config.min_samples > config.max_samples {
+            return Err("--min-samples must not exceed --max-samples".into());
+        }
+
+        Ok(config)
+    }
+
+    /// Reads the benchmark options that were passed to the solution binary.
+    fn from_env() -> Self {
+        let mut args = pico_args::Arguments::from_env();
+        match Self::parse(&mut args) {
+            Ok(config) => config,
+            Err(e) => {
+                eprintln!("Invalid benchmark options: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    /// Converts the options back into command-line arguments, e.g. to forward them to a solution binary.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        let default = Self::default();
+        let mut args = vec![];
+
+        if self.budget != default.budget {
+            args.push("--bench-time".into());
+            args.push(self.budget.as_secs_f64().to_string());
+        }
+
+        if self.min_samples != default.min_samples {
+            args.push("--min-samples".into());
+            args.push(self.min_samples.to_string());
+        }
+
+        if self.max_samples != default.max_samples {
+            args.push("--max-samples".into());
+            args.push(self.max_samples.to_string());
+        }
+
+        if self.warmup != default.warmup {
+            args.push("--warmup".into());
+            args.push(self.warmup.to_string());
+        }
+
+        if self.reject_outliers {
+            args.push("--reject-outliers".into());
+        }
+
+        args
+    }
+}
+
+/// The statistic of a benchmark that is reported as "the" timing of a solution part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Statistic {
+    #[default]
+    Mean,
+    Median,
+}
+
+impl FromStr for Statistic {
+    type Err = StatisticFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "mean" => Ok(Self::Mean),
+            "median" => Ok(Self::Median),
+            _ => Err(StatisticFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`Statistic`].
+#[derive(Debug)]
+pub struct StatisticFromStrError;
+
+impl Error for StatisticFromStrError {}
+
+impl Display for StatisticFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting one of `mean` or `median`")
+    }
+}
+
+/// Summary of the samples that were collected while running a solution part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchStats {
+    pub samples: u128,
+    /// Number of samples that were discarded as outliers.
+    pub outliers: u128,
+    pub mean: Duration,
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub max: Duration,
+    pub std_dev: Duration,
+}
+
+impl BenchStats {
+    /// Computes the statistics for a non-empty set of samples.
+    #[must_use]
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "cannot summarize an empty benchmark");
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        let n = sorted.len();
+        let mean_nanos = sorted.iter().map(Duration::as_nanos).sum::<u128>() / n as u128;
+
+        let median = if n.is_multiple_of(2) {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        } else {
+            sorted[n / 2]
+        };
+
+        // nearest-rank method: the smallest sample that is >= 95% of all samples.
+        let p95 = sorted[(n * 95).div_ceil(100) - 1];
+
+        #[allow(clippy::cast_precision_loss)]
+        let variance = sorted
+            .iter()
+            .map(|sample| (sample.as_nanos() as f64 - mean_nanos as f64).powi(2))
+            .sum::<f64>()
+            / n as f64;
+
+        #[allow(clippy::cast_possible_truncation)]
+        Self {
+            samples: n as u128,
+            outliers: 0,
+            mean: Duration::from_nanos(mean_nanos as u64),
+            min: sorted[0],
+            median,
+            p95,
+            max: sorted[n - 1],
+            std_dev: Duration::from_secs_f64(variance.sqrt() / 1e9),
+        }
+    }
+
+    /// Computes the statistics for a non-empty set of samples after discarding samples outside of
+    /// the Tukey fences, i.e. further than 1.5 times the interquartile range away from the quartiles.
+    #[must_use]
+    pub fn from_samples_without_outliers(samples: &[Duration]) -> Self {
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        let n = sorted.len();
+        let q1 = sorted[n / 4];
+        let q3 = sorted[(n * 3) / 4];
+        let fence = (q3 - q1) * 3 / 2;
+
+        let lower = q1.saturating_sub(fence);
+        let upper = q3 + fence;
+
+        let kept: Vec<Duration> = sorted
+            .into_iter()
+            .filter(|sample| (lower..=upper).contains(sample))
+            .collect();
+
+        Self {
+            outliers: (n - kept.len()) as u128,
+            ..Self::from_samples(&kept)
+        }
+    }
+
+    /// Returns the duration that corresponds to a [`Statistic`].
+    #[must_use]
+    pub fn get(&self, statistic: Statistic) -> Duration {
+        match statistic {
+            Statistic::Mean => self.mean,
+            Statistic::Median => self.median,
+        }
+    }
+}
+
+/// Formats the timing of a part. For benched parts, a second line with the spread of the samples is appended.
+fn format_duration(stats: &BenchStats) -> String {
+    let BenchStats {
+        samples,
+        outliers,
+        mean,
+        min,
+        median,
+        p95,
+        max,
+        std_dev,
+    } = stats;
+
+    if *samples == 1 && *outliers == 0 {
+        return format!(" ({mean:.1?})");
+    }
+
+    let mut str = format!(
+        " ({mean:.1?} @ {samples} samples)\n{STATS_PREFIX}min {min:.1?} | median {median:.1?} | p95 {p95:.1?} | max {max:.1?} | σ {std_dev:.1?}"
+    );
+
+    if *outliers > 0 {
+        str.push_str(&format!(" | {outliers} outliers rejected"));
     }
+
+    str
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result(answer: &Result<Option<String>, String>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
-    match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
+    match answer {
+        Ok(Some(result)) => {
+            if result.contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
                     print!("{str}");
@@ -116,7 +538,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
-        None => {
+        Ok(None) => {
             if is_intermediate_result {
                 print!("{part}: ✖");
             } else {
@@ -124,44 +546,278 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 println!("{part}: ✖             ");
             }
         }
+        Err(e) => {
+            if is_intermediate_result {
+                print!("{part}: ✖");
+            } else {
+                print!("\r");
+                println!("{part}: ✖ {ANSI_ITALIC}{e}{ANSI_RESET}{duration_str}");
+            }
+        }
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
+/// Reads the `--submit` option that was passed to the solution binary.
+/// Only answers for the puzzle input can be submitted, so it is refused together with `--input`.
+fn submit_part() -> Option<Part> {
+    let mut args = pico_args::Arguments::from_env();
+    match args.opt_value_from_str("--submit") {
+        Ok(Some(_)) if input_path().is_some() => {
+            eprintln!("Answers for a custom --input cannot be submitted.");
+            process::exit(1);
+        }
+        Ok(part) => part,
+        Err(e) => {
+            eprintln!("Invalid part to submit: {e}");
+            process::exit(1);
+        }
+    }
+}
 
-    if !args.contains(&"--submit".into()) {
+/// Try to submit one part of the solution if:
+///  1. it is the part passed via `--submit`.
+///  2. Advent of Code is accessible.
+fn submit_result(
+    answer: String,
+    puzzle: PuzzleId,
+    part: Part,
+) -> Option<Result<SubmitResponse, AocCommandError>> {
+    if submit_part() != Some(part) {
         return None;
     }
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
-    }
+    let backend = match Backend::from_env() {
+        Ok(backend) => backend,
+        Err(e) => {
+            eprintln!("Advent of Code is not accessible: {e}");
+            eprintln!(
+                "Configure a session token or run \"cargo install aoc-cli\" to install aoc-cli."
+            );
+            process::exit(1);
+        }
+    };
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+    let mut ledger = match Ledger::load(puzzle) {
+        Ok(ledger) => ledger,
+        Err(e) => {
+            eprintln!("Failed to load submissions of {puzzle}: {e}");
+            process::exit(1);
+        }
+    };
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    let mut cooldown = match Cooldown::load() {
+        Ok(cooldown) => cooldown,
+        Err(e) => {
+            eprintln!("Failed to load the submission cooldown: {e}");
+            process::exit(1);
+        }
     };
 
-    if part_submit != part {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+
+    submit_checked(
+        &backend,
+        &mut ledger,
+        &mut cooldown,
+        puzzle,
+        part,
+        answer,
+        now,
+    )
+}
+
+/// Submits an answer unless the cooldown or the ledger of the day refuse it, and records the response in both.
+fn submit_checked(
+    backend: &Backend,
+    ledger: &mut Ledger,
+    cooldown: &mut Cooldown,
+    puzzle: PuzzleId,
+    part: Part,
+    answer: String,
+    now: u64,
+) -> Option<Result<SubmitResponse, AocCommandError>> {
+    if let Err(refusal) = cooldown
+        .check(now)
+        .and_then(|()| ledger.check(part, &answer))
+    {
+        eprintln!("Not submitting \"{answer}\": {refusal}");
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    println!("Submitting result...");
+    let result = aoc_cli::submit_with(backend, puzzle, part, &answer);
+
+    if let Err(e) = &result {
+        eprintln!("Failed to submit: {e}");
+    }
+
+    if let Ok(response) = result {
+        let submission = Submission {
+            part,
+            answer,
+            response,
+            timestamp: now,
+        };
+
+        if let Err(e) = cooldown.record(&submission) {
+            eprintln!("Failed to record the submission cooldown: {e}");
+        }
+
+        if let Err(e) = ledger.record(submission) {
+            eprintln!("Failed to record submission: {e}");
+        }
+    }
+
+    Some(result)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{submit_checked, BenchConfig, BenchStats, Statistic};
+    use crate::template::aoc_cli::{Backend, Hint, SubmitResponse};
+    use crate::template::aoc_client::{tests::mock_server, Client};
+    use crate::template::submissions::{Cooldown, Ledger, Refusal};
+    use crate::{day, year, Part, PuzzleId};
+    use std::ffi::OsString;
+    use std::time::Duration;
+    use std::{env, fs};
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().copied().map(Duration::from_nanos).collect()
+    }
+
+    #[test]
+    fn stats_of_single_sample() {
+        let stats = BenchStats::from_samples(&nanos(&[42]));
+        assert_eq!(stats.samples, 1);
+        assert_eq!(stats.mean, Duration::from_nanos(42));
+        assert_eq!(stats.median, Duration::from_nanos(42));
+        assert_eq!(stats.p95, Duration::from_nanos(42));
+        assert_eq!(stats.std_dev, Duration::ZERO);
+    }
+
+    #[test]
+    fn stats_are_robust_against_outliers() {
+        let stats = BenchStats::from_samples(&nanos(&[10, 12, 11, 10, 1000, 11, 12, 10, 11, 13]));
+        assert_eq!(stats.samples, 10);
+        assert_eq!(stats.min, Duration::from_nanos(10));
+        assert_eq!(stats.max, Duration::from_nanos(1000));
+        assert_eq!(stats.mean, Duration::from_nanos(110));
+        assert_eq!(stats.get(Statistic::Median), Duration::from_nanos(11));
+        assert_eq!(stats.p95, Duration::from_nanos(1000));
+        assert_eq!(stats.std_dev, Duration::from_nanos(297));
+    }
+
+    #[test]
+    fn median_of_even_sample_count() {
+        let stats = BenchStats::from_samples(&nanos(&[4, 1, 3, 2]));
+        assert_eq!(stats.median, Duration::from_nanos(2));
+        assert_eq!(stats.p95, Duration::from_nanos(4));
+    }
+
+    #[test]
+    fn rejects_outliers_outside_tukey_fences() {
+        let stats = BenchStats::from_samples_without_outliers(&nanos(&[
+            10, 12, 11, 10, 1000, 11, 12, 10, 11, 13,
+        ]));
+        assert_eq!(stats.samples, 9);
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.max, Duration::from_nanos(13));
+        assert_eq!(stats.mean, Duration::from_nanos(11));
+    }
+
+    #[test]
+    fn bench_config_round_trips_through_args() {
+        let config = BenchConfig {
+            budget: Duration::from_millis(2500),
+            min_samples: 50,
+            max_samples: 500,
+            warmup: 3,
+            reject_outliers: true,
+        };
+
+        let mut args = pico_args::Arguments::from_vec(
+            config.to_args().into_iter().map(OsString::from).collect(),
+        );
+
+        assert_eq!(BenchConfig::parse(&mut args).unwrap(), config);
+        assert!(args.finish().is_empty());
+    }
+
+    #[test]
+    fn bench_config_defaults_to_no_args() {
+        assert!(BenchConfig::default().to_args().is_empty());
+    }
+
+    #[test]
+    fn bench_config_rejects_inverted_bounds() {
+        let mut args = pico_args::Arguments::from_vec(
+            ["--min-samples", "100", "--max-samples", "10"]
+                .into_iter()
+                .map(OsString::from)
+                .collect(),
+        );
+        assert!(BenchConfig::parse(&mut args).is_err());
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    #[test]
+    fn submits_answers_and_keeps_the_cooldown_for_all_days() {
+        let dir = env::temp_dir().join("aoc-submit-result");
+        let _ = fs::remove_dir_all(&dir);
+        let path = |name: &str| dir.join(name).to_string_lossy().to_string();
+
+        let (url, requests) = mock_server(
+            200,
+            "<main><article><p>That's not the right answer; your answer is too high. Please wait one minute before trying again.</p></article></main>",
+        );
+        let backend = Backend::Native(Client::new(&url, "secret".into(), "test-agent"));
+        let too_high = SubmitResponse::Incorrect {
+            hint: Some(Hint::TooHigh),
+            wait: Some(Duration::from_secs(60)),
+        };
+
+        let mut cooldown = Cooldown::load_path(&path("cooldown")).unwrap();
+        let mut day_3 = Ledger::load_path(&path("03.jsonl")).unwrap();
+        let result = submit_checked(
+            &backend,
+            &mut day_3,
+            &mut cooldown,
+            PuzzleId::new(year!(2023), day!(3)),
+            Part::One,
+            "42".into(),
+            1000,
+        );
+        assert_eq!(result.unwrap().unwrap(), too_high);
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("POST /2023/day/3/answer HTTP/1.1"));
+        assert!(request.ends_with("level=1&answer=42"));
+
+        // the next process refuses another day during the cooldown without contacting the server.
+        let mut cooldown = Cooldown::load_path(&path("cooldown")).unwrap();
+        let mut day_4 = Ledger::load_path(&path("04.jsonl")).unwrap();
+        assert_eq!(
+            cooldown.check(1030),
+            Err(Refusal::CoolingDown(Duration::from_secs(30)))
+        );
+        let result = submit_checked(
+            &backend,
+            &mut day_4,
+            &mut cooldown,
+            PuzzleId::new(year!(2023), day!(4)),
+            Part::One,
+            "7".into(),
+            1030,
+        );
+        assert!(result.is_none());
+
+        let day_3 = Ledger::load_path(&path("03.jsonl")).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(
+            day_3.check(Part::One, "42"),
+            Err(Refusal::KnownWrong(too_high))
+        );
+    }
 }
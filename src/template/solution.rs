@@ -0,0 +1,77 @@
+/// An optional way to write a solution that parses its input once for both parts.
+/// The runner times and benches the parse step separately from the parts.
+use std::fmt::Display;
+
+use crate::template::runner::PartOutput;
+
+/// A solution that parses its input once and solves both parts from the parsed input.
+///
+/// It is passed to `solution!` after the day, e.g. `solution!(14 => Dish)`, which also creates
+/// `part_one` and `part_two` functions that take the raw input, so tests and `examples!` work unchanged.
+///
+/// ```ignore
+/// advent_of_code::solution!(1 => Calibration);
+///
+/// pub struct Calibration;
+///
+/// impl advent_of_code::template::Solution for Calibration {
+///     type Parsed = Vec<u32>;
+///     type Error = std::num::ParseIntError;
+///     type OutputOne = Option<u32>;
+///     type OutputTwo = Option<u32>;
+///
+///     fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+///         input.lines().map(str::parse).collect()
+///     }
+///
+///     fn part_one(numbers: &Self::Parsed) -> Option<u32> {
+///         numbers.iter().max().copied()
+///     }
+///
+///     fn part_two(numbers: &Self::Parsed) -> Option<u32> {
+///         Some(numbers.iter().sum())
+///     }
+/// }
+/// ```
+pub trait Solution {
+    /// The input after parsing, which is shared by both parts.
+    type Parsed;
+    /// The error of an input that cannot be parsed, which is reported as the error of both parts.
+    type Error: Display;
+    /// What the parts return, e.g. `Option<u32>` or `Result<u32, String>` like part functions.
+    type OutputOne: PartOutput;
+    type OutputTwo: PartOutput;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error>;
+    fn part_one(parsed: &Self::Parsed) -> Self::OutputOne;
+    fn part_two(parsed: &Self::Parsed) -> Self::OutputTwo;
+}
+
+/// What the `part_one` and `part_two` functions that `solution!` creates for a [`Solution`] return:
+/// the output of the part, or the error of parsing the input.
+pub struct SolutionOutput<O>(pub Result<O, String>);
+
+impl<O: PartOutput> PartOutput for SolutionOutput<O> {
+    fn answer(&self) -> Result<Option<String>, String> {
+        match &self.0 {
+            Ok(output) => output.answer(),
+            Err(e) => Err(e.clone()),
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::SolutionOutput;
+    use crate::template::runner::PartOutput;
+
+    #[test]
+    fn reports_parse_errors_as_answer() {
+        assert_eq!(SolutionOutput(Ok(Some(42))).answer(), Ok(Some("42".into())));
+        assert_eq!(SolutionOutput::<Option<u32>>(Ok(None)).answer(), Ok(None));
+        assert_eq!(
+            SolutionOutput::<Option<u32>>(Err("unexpected space".into())).answer(),
+            Err("unexpected space".into())
+        );
+    }
+}
@@ -0,0 +1,474 @@
+/// Module that keeps a ledger of all answers submitted for a day in `data/submissions/NN.jsonl`.
+/// The ledger is consulted before submitting to avoid answers that are known to be wrong.
+use std::fmt::Display;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::Duration;
+
+use tinyjson::JsonValue;
+
+use crate::template::aoc_cli::{Hint, SubmitResponse};
+use crate::{Part, PuzzleId};
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "could not parse submission ledger: {e}"),
+            Error::IO(e) => write!(f, "could not access submission ledger: {e}"),
+        }
+    }
+}
+
+/// A single submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    pub part: Part,
+    pub answer: String,
+    pub response: SubmitResponse,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+}
+
+impl Submission {
+    fn to_json(&self) -> String {
+        let verdict = match self.response {
+            SubmitResponse::Correct => "correct",
+            SubmitResponse::Incorrect {
+                hint: Some(Hint::TooHigh),
+                ..
+            } => "too_high",
+            SubmitResponse::Incorrect {
+                hint: Some(Hint::TooLow),
+                ..
+            } => "too_low",
+            SubmitResponse::Incorrect { hint: None, .. } => "incorrect",
+            SubmitResponse::RateLimited { .. } => "rate_limited",
+            SubmitResponse::AlreadySolved => "already_solved",
+            SubmitResponse::WrongLevel => "wrong_level",
+        };
+
+        JsonValue::Object(
+            [
+                ("part", JsonValue::Number(f64::from(self.part.into_inner()))),
+                ("answer", JsonValue::String(self.answer.clone())),
+                ("verdict", JsonValue::String(verdict.into())),
+                (
+                    "timestamp",
+                    #[allow(clippy::cast_precision_loss)]
+                    JsonValue::Number(self.timestamp as f64),
+                ),
+                (
+                    "cooldown_secs",
+                    #[allow(clippy::cast_precision_loss)]
+                    self.response
+                        .wait()
+                        .map_or(JsonValue::Null, |c| JsonValue::Number(c.as_secs() as f64)),
+                ),
+            ]
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), value))
+            .collect(),
+        )
+        .stringify()
+        .expect("submissions only contain finite numbers")
+    }
+
+    fn from_json(line: &str) -> Option<Self> {
+        let JsonValue::Object(fields) = line.parse::<JsonValue>().ok()? else {
+            return None;
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let number = |value: &JsonValue| match value {
+            JsonValue::Number(n) => Some(*n as u64),
+            _ => None,
+        };
+
+        let JsonValue::String(answer) = fields.get("answer")? else {
+            return None;
+        };
+
+        let JsonValue::String(verdict) = fields.get("verdict")? else {
+            return None;
+        };
+
+        let wait = match fields.get("cooldown_secs") {
+            None | Some(JsonValue::Null) => None,
+            Some(value) => Some(Duration::from_secs(number(value)?)),
+        };
+
+        let incorrect = |hint| SubmitResponse::Incorrect { hint, wait };
+
+        let response = match verdict.as_str() {
+            "correct" => SubmitResponse::Correct,
+            "too_high" => incorrect(Some(Hint::TooHigh)),
+            "too_low" => incorrect(Some(Hint::TooLow)),
+            "incorrect" => incorrect(None),
+            "rate_limited" => SubmitResponse::RateLimited { wait },
+            "already_solved" => SubmitResponse::AlreadySolved,
+            "wrong_level" => SubmitResponse::WrongLevel,
+            _ => return None,
+        };
+
+        Some(Self {
+            part: Part::new(u8::try_from(number(fields.get("part")?)?).ok()?)?,
+            answer: answer.clone(),
+            response,
+            timestamp: number(fields.get("timestamp")?)?,
+        })
+    }
+}
+
+/// The reason why an answer is not submitted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    /// The part has already been solved, with the correct answer if it was submitted from here.
+    AlreadySolved(Option<String>),
+    /// The exact answer has been rejected before.
+    KnownWrong(SubmitResponse),
+    /// The answer lies outside the bounds learned from earlier "too high" / "too low" verdicts.
+    OutOfBounds {
+        lower: Option<i128>,
+        upper: Option<i128>,
+    },
+    /// The server asked to wait before submitting again.
+    CoolingDown(Duration),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadySolved(Some(answer)) => {
+                write!(f, "this part was already solved with \"{answer}\".")
+            }
+            Refusal::AlreadySolved(None) => write!(f, "this part was already solved."),
+            Refusal::KnownWrong(verdict) => {
+                write!(f, "this answer was submitted before and was {verdict}.")
+            }
+            Refusal::OutOfBounds { lower, upper } => {
+                let lower = lower.map_or("-∞".into(), |x| x.to_string());
+                let upper = upper.map_or("∞".into(), |x| x.to_string());
+                write!(
+                    f,
+                    "the answer has to lie between {lower} and {upper} (exclusive)."
+                )
+            }
+            Refusal::CoolingDown(wait) => {
+                write!(
+                    f,
+                    "please wait {}s before submitting again.",
+                    wait.as_secs()
+                )
+            }
+        }
+    }
+}
+
+/// Stores until when the server asked to wait before submitting again. The wait applies to the whole account,
+/// so it is kept in one place for all days and years.
+const COOLDOWN_PATH: &str = "data/cache/submit-cooldown";
+
+/// The time until which the server refuses answers of any day, shared by all processes.
+#[derive(Debug, Clone)]
+pub struct Cooldown {
+    path: String,
+    /// Seconds since the unix epoch.
+    until: Option<u64>,
+}
+
+impl Cooldown {
+    /// Loads the cooldown of the account. A missing file is treated as no cooldown.
+    pub fn load() -> Result<Self, Error> {
+        Self::load_path(COOLDOWN_PATH)
+    }
+
+    /// Loads the cooldown from a file in the format of [`Cooldown::load`].
+    pub fn load_path(path: &str) -> Result<Self, Error> {
+        let until = match fs::read_to_string(path) {
+            Ok(content) => Some(
+                content
+                    .trim()
+                    .parse::<u64>()
+                    .map_err(|_| Error::Parser(format!("cooldown \"{}\"", content.trim())))?,
+            ),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(Self {
+            path: path.into(),
+            until,
+        })
+    }
+
+    /// Checks whether the wait is over at the given time (in seconds since the unix epoch).
+    pub fn check(&self, now: u64) -> Result<(), Refusal> {
+        match self.until.and_then(|until| until.checked_sub(now)) {
+            Some(remaining) if remaining > 0 => {
+                Err(Refusal::CoolingDown(Duration::from_secs(remaining)))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Extends the cooldown by the wait the server asked for in response to a submission.
+    pub fn record(&mut self, submission: &Submission) -> Result<(), Error> {
+        let Some(wait) = submission.response.wait() else {
+            return Ok(());
+        };
+
+        let until = submission.timestamp + wait.as_secs();
+        if self.until.is_some_and(|current| current >= until) {
+            return Ok(());
+        }
+
+        if let Some(dir) = Path::new(&self.path).parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        fs::write(&self.path, until.to_string())?;
+        self.until = Some(until);
+        Ok(())
+    }
+}
+
+/// All submissions of a day.
+#[derive(Debug, Clone)]
+pub struct Ledger {
+    path: String,
+    submissions: Vec<Submission>,
+}
+
+#[must_use]
+pub fn get_path(puzzle: PuzzleId) -> String {
+    puzzle.data_file("submissions", "jsonl")
+}
+
+impl Ledger {
+    /// Loads the ledger of a day. A missing file is treated as an empty ledger.
+    pub fn load(puzzle: PuzzleId) -> Result<Self, Error> {
+        Self::load_path(&get_path(puzzle))
+    }
+
+    /// Loads a ledger from a file in the format of `data/submissions/NN.jsonl`.
+    pub fn load_path(path: &str) -> Result<Self, Error> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e.into()),
+        };
+
+        let submissions = content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .enumerate()
+            .map(|(i, line)| {
+                Submission::from_json(line).ok_or_else(|| Error::Parser(format!("line {}", i + 1)))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            path: path.into(),
+            submissions,
+        })
+    }
+
+    /// Checks whether an answer should be submitted. The wait the server asks for is checked by [`Cooldown`].
+    pub fn check(&self, part: Part, answer: &str) -> Result<(), Refusal> {
+        let submissions = || self.submissions.iter().filter(move |s| s.part == part);
+
+        if let Some(solved) = submissions().find(|s| s.response == SubmitResponse::Correct) {
+            return Err(Refusal::AlreadySolved(Some(solved.answer.clone())));
+        }
+
+        if submissions().any(|s| s.response == SubmitResponse::AlreadySolved) {
+            return Err(Refusal::AlreadySolved(None));
+        }
+
+        if let Some(wrong) = submissions().find(|s| s.answer == answer && s.response.is_incorrect())
+        {
+            return Err(Refusal::KnownWrong(wrong.response));
+        }
+
+        if let Ok(value) = answer.trim().parse::<i128>() {
+            let bound = |hint: Hint| {
+                submissions()
+                    .filter(move |s| {
+                        matches!(s.response, SubmitResponse::Incorrect { hint: Some(h), .. } if h == hint)
+                    })
+                    .filter_map(|s| s.answer.trim().parse::<i128>().ok())
+            };
+
+            let lower = bound(Hint::TooLow).max();
+            let upper = bound(Hint::TooHigh).min();
+
+            if lower.is_some_and(|lower| value <= lower)
+                || upper.is_some_and(|upper| value >= upper)
+            {
+                return Err(Refusal::OutOfBounds { lower, upper });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Adds a submission to the ledger and appends it to the ledger file.
+    pub fn record(&mut self, submission: Submission) -> Result<(), Error> {
+        if let Some(dir) = Path::new(&self.path).parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+
+        writeln!(file, "{}", submission.to_json())?;
+        self.submissions.push(submission);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Cooldown, Ledger, Refusal, Submission};
+    use crate::template::aoc_cli::{Hint, SubmitResponse};
+    use crate::Part;
+    use std::time::Duration;
+    use std::{env, fs};
+
+    const TOO_HIGH: SubmitResponse = SubmitResponse::Incorrect {
+        hint: Some(Hint::TooHigh),
+        wait: None,
+    };
+
+    const TOO_LOW: SubmitResponse = SubmitResponse::Incorrect {
+        hint: Some(Hint::TooLow),
+        wait: None,
+    };
+
+    fn ledger(submissions: &[(Part, &str, SubmitResponse, u64)]) -> Ledger {
+        Ledger {
+            path: String::new(),
+            submissions: submissions
+                .iter()
+                .map(|(part, answer, response, timestamp)| Submission {
+                    part: *part,
+                    answer: (*answer).into(),
+                    response: *response,
+                    timestamp: *timestamp,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let submission = Submission {
+            part: Part::Two,
+            answer: "42".into(),
+            response: SubmitResponse::Incorrect {
+                hint: Some(Hint::TooLow),
+                wait: Some(Duration::from_secs(60)),
+            },
+            timestamp: 1_700_000_000,
+        };
+        assert_eq!(
+            Submission::from_json(&submission.to_json()),
+            Some(submission)
+        );
+    }
+
+    #[test]
+    fn blocks_known_wrong_answers() {
+        let incorrect = SubmitResponse::Incorrect {
+            hint: None,
+            wait: None,
+        };
+        let ledger = ledger(&[(Part::One, "abc", incorrect, 0)]);
+        assert_eq!(
+            ledger.check(Part::One, "abc"),
+            Err(Refusal::KnownWrong(incorrect))
+        );
+        assert_eq!(ledger.check(Part::One, "abd"), Ok(()));
+        assert_eq!(ledger.check(Part::Two, "abc"), Ok(()));
+    }
+
+    #[test]
+    fn blocks_answers_outside_of_bounds() {
+        let ledger = ledger(&[
+            (Part::One, "100", TOO_HIGH, 0),
+            (Part::One, "200", TOO_HIGH, 0),
+            (Part::One, "10", TOO_LOW, 0),
+        ]);
+
+        let refusal = Err(Refusal::OutOfBounds {
+            lower: Some(10),
+            upper: Some(100),
+        });
+
+        assert_eq!(ledger.check(Part::One, "150"), refusal);
+        assert_eq!(ledger.check(Part::One, "5"), refusal);
+        assert_eq!(ledger.check(Part::One, "101"), refusal);
+        assert_eq!(ledger.check(Part::One, "50"), Ok(()));
+        assert_eq!(ledger.check(Part::One, "not a number"), Ok(()));
+    }
+
+    #[test]
+    fn blocks_submissions_of_any_day_during_cooldown() {
+        let path = env::temp_dir().join("aoc-submit-cooldown");
+        let path = path.to_string_lossy();
+        let _ = fs::remove_file(path.as_ref());
+
+        let mut cooldown = Cooldown::load_path(&path).unwrap();
+        assert_eq!(cooldown.check(1000), Ok(()));
+
+        let rate_limited = Submission {
+            part: Part::One,
+            answer: "1".into(),
+            response: SubmitResponse::RateLimited {
+                wait: Some(Duration::from_secs(90)),
+            },
+            timestamp: 1000,
+        };
+        cooldown.record(&rate_limited).unwrap();
+
+        // the cooldown is not tied to a day, so another process submitting any day sees it.
+        let cooldown = Cooldown::load_path(&path).unwrap();
+        fs::remove_file(path.as_ref()).unwrap();
+        assert_eq!(
+            cooldown.check(1030),
+            Err(Refusal::CoolingDown(Duration::from_secs(60)))
+        );
+        assert_eq!(cooldown.check(1090), Ok(()));
+    }
+
+    #[test]
+    fn blocks_solved_parts() {
+        let ledger = ledger(&[
+            (Part::One, "42", SubmitResponse::Correct, 0),
+            (Part::Two, "7", SubmitResponse::AlreadySolved, 0),
+        ]);
+        assert_eq!(
+            ledger.check(Part::One, "43"),
+            Err(Refusal::AlreadySolved(Some("42".into())))
+        );
+        assert_eq!(
+            ledger.check(Part::Two, "8"),
+            Err(Refusal::AlreadySolved(None))
+        );
+    }
+}